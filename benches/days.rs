@@ -0,0 +1,41 @@
+use aoc2020::Solution;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::fs;
+
+// bench_day benchmarks parsing and each implemented part of a solution, reading the
+// input from `src/dayNN/input.txt` unless the day has a built-in input.
+#[allow(dead_code)]
+fn bench_day<S: Solution>(c: &mut Criterion, number: u32) {
+    let input = if S::NEEDS_INPUT {
+        fs::read_to_string(format!("src/day{:02}/input.txt", number)).expect("reading input")
+    } else {
+        String::new()
+    };
+    let parsed = S::parse(&input).expect("parsing input");
+    let part1 = if S::PART2_NEEDS_PART1 {S::part1(&parsed).ok()} else {None};
+    let mut group = c.benchmark_group(format!("day{:02}", number));
+    group.sample_size(10);
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(&input))));
+    if S::PARTS.contains(&1) {
+        group.bench_function("part1", |b| b.iter(|| S::part1(black_box(&parsed))));
+    }
+    // Day 15 part 2 takes seconds per run, use `aoc2020 bench 15` for it instead.
+    if S::PARTS.contains(&2) && number != 15 {
+        group.bench_function("part2", |b| b.iter(|| S::part2(black_box(&parsed), part1.as_ref())));
+    }
+    group.finish();
+}
+
+// days benchmarks every day built, which may be none of them.
+#[allow(unused_macros, unused_variables)]
+fn days(c: &mut Criterion) {
+    macro_rules! bench {
+        ($number:expr, $module:ident, $day:ident) => {
+            bench_day::<aoc2020::$module::$day>(c, $number);
+        };
+    }
+    aoc2020::for_each_day!(bench);
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
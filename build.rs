@@ -0,0 +1,146 @@
+use std::{env, fs, path::{Path, PathBuf}, process};
+
+// fail stops the build with an error message.
+fn fail(msg: String) -> ! {
+    eprintln!("error: {}", msg);
+    process::exit(1);
+}
+
+// find_days returns the number of every `src/dayNN` directory, checking that each one
+// has a `mod.rs` defining the day's `DayNN` solution.
+fn find_days(src: &Path) -> Vec<u32> {
+    let mut days = Vec::new();
+    for entry in fs::read_dir(src).expect("reading src") {
+        let entry = entry.expect("reading src");
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let number = match name.strip_prefix("day").and_then(|n| n.parse::<u32>().ok()) {
+            Some(n) if entry.path().is_dir() => n,
+            _ => continue,
+        };
+        if name != format!("day{:02}", number) {
+            fail(format!("src/{} should be named src/day{:02}", name, number));
+        }
+        let module = entry.path().join("mod.rs");
+        let source = fs::read_to_string(&module).unwrap_or_else(|_| {
+            fail(format!("day {} is declared by src/{} but {} is missing", number, name, module.display()))
+        });
+        if !source.contains(&format!("pub struct Day{:02};", number)) {
+            fail(format!("{} does not define `pub struct Day{:02};`", module.display(), number));
+        }
+        days.push(number);
+    }
+    days.sort_unstable();
+    days
+}
+
+// day_features returns the `dayNN` features declared in the manifest's `[features]`
+// table.
+fn day_features(manifest: &str) -> Vec<String> {
+    let mut section = "";
+    let mut features = Vec::new();
+    for line in manifest.lines().map(str::trim) {
+        if line.starts_with('[') {
+            section = line;
+        } else if section == "[features]" && line.starts_with("day") {
+            features.push(line.split('=').next().unwrap().trim().to_owned());
+        }
+    }
+    features
+}
+
+// Example is a worked example input and the expected answer for each part it is for.
+struct Example {
+    name: String,
+    path: PathBuf,
+    answers: Vec<(u32, String)>,
+}
+
+// find_examples returns the examples for a day: each `examples/NAME.txt` with a
+// sidecar `examples/NAME.answers` listing `part,answer` lines.
+fn find_examples(day: &Path) -> Vec<Example> {
+    let dir = day.join("examples");
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+    let mut examples = Vec::new();
+    for entry in entries {
+        let path = entry.expect("reading examples").path();
+        if path.extension().is_none_or(|ext| ext != "txt") {
+            continue;
+        }
+        let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+        let answers_path = path.with_extension("answers");
+        let answers = fs::read_to_string(&answers_path).unwrap_or_else(|_| {
+            fail(format!("{} has no {}", path.display(), answers_path.display()))
+        });
+        let answers = answers.lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty() && !l.starts_with('#'))
+            .map(|(i, l)| {
+                let mut fields = l.trim().splitn(2, ',');
+                match (fields.next().and_then(|p| p.parse().ok()), fields.next()) {
+                    (Some(part), Some(answer)) => (part, answer.to_owned()),
+                    _ => fail(format!("{}:{}: expected `part,answer`", answers_path.display(), i + 1)),
+                }
+            })
+            .collect();
+        examples.push(Example{name, path, answers});
+    }
+    examples.sort_by(|a, b| a.name.cmp(&b.name));
+    examples
+}
+
+// Generates the registry of days which lib.rs includes: a module for each day, the
+// DAYS table and the for_each_day! macro. Each day has a `dayNN` feature, and days
+// whose feature is not enabled are left out.
+fn main() {
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=Cargo.toml");
+    let root = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).to_owned();
+    let src = root.join("src");
+    let manifest = fs::read_to_string(root.join("Cargo.toml")).expect("reading Cargo.toml");
+    let features = day_features(&manifest);
+    let mut days = find_days(&src);
+    for n in days.iter() {
+        let feature = format!("day{:02}", n);
+        if !features.contains(&feature) {
+            fail(format!("src/{} needs a `{}` feature in Cargo.toml, listed in `all-days`", feature, feature));
+        }
+    }
+    days.retain(|n| env::var_os(format!("CARGO_FEATURE_DAY{:02}", n)).is_some());
+
+    let mut out = String::new();
+    for n in days.iter() {
+        let path = src.join(format!("day{:02}", n)).join("mod.rs");
+        out.push_str(&format!("#[path = {:?}]\npub mod day{:02};\n", path.display().to_string(), n));
+    }
+    out.push_str("\npub const DAYS: &[Day] = &[\n");
+    for n in days.iter() {
+        out.push_str(&format!("    Day::new::<day{:02}::Day{:02}>({}),\n", n, n, n));
+    }
+    out.push_str("];\n\n#[macro_export]\nmacro_rules! for_each_day {\n    ($m:ident) => {\n");
+    for n in days.iter() {
+        out.push_str(&format!("        $m!({}, day{:02}, Day{:02});\n", n, n, n));
+    }
+    out.push_str("    };\n}\n");
+
+    let out_dir = Path::new(&env::var("OUT_DIR").unwrap()).to_owned();
+    fs::write(out_dir.join("days.rs"), out).expect("writing days.rs");
+
+    // The example tests, which tests/examples.rs includes.
+    let mut out = String::new();
+    for n in days.iter() {
+        for Example{name, path, answers} in find_examples(&src.join(format!("day{:02}", n))) {
+            let name: String = name.chars()
+                .map(|c| if c.is_ascii_alphanumeric() {c.to_ascii_lowercase()} else {'_'})
+                .collect();
+            for (part, answer) in answers {
+                out.push_str(&format!(
+                    "#[test]\nfn day{:02}_{}_part{}() {{\n    check({}, {}, {:?}, {:?});\n}}\n\n",
+                    n, name, part, n, part, path.display().to_string(), answer));
+            }
+        }
+    }
+    fs::write(out_dir.join("examples.rs"), out).expect("writing examples.rs");
+}
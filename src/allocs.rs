@@ -0,0 +1,137 @@
+use crate::error::ParseError;
+use crate::solution::Solution;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Whether allocations are being counted. Counting needs the `alloc-count` feature,
+/// which installs `Counting` as the global allocator. Without it every Stats is zero.
+pub const ENABLED: bool = cfg!(feature = "alloc-count");
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+static CURRENT: AtomicU64 = AtomicU64::new(0);
+static PEAK: AtomicU64 = AtomicU64::new(0);
+
+/// Counting wraps the system allocator, counting allocations and the bytes allocated and
+/// keeping track of the peak heap size.
+pub struct Counting;
+
+// grow records an allocation of `size` bytes which replaces `old` bytes, as when a
+// Vec is reallocated.
+fn grow(size: usize, old: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(size as u64, Ordering::Relaxed);
+    let current = if size >= old {
+        let d = (size - old) as u64;
+        CURRENT.fetch_add(d, Ordering::Relaxed) + d
+    } else {
+        let d = (old - size) as u64;
+        CURRENT.fetch_sub(d, Ordering::Relaxed) - d
+    };
+    PEAK.fetch_max(current, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        grow(layout.size(), 0);
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        grow(layout.size(), 0);
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        CURRENT.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        grow(new_size, layout.size());
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[cfg(feature = "alloc-count")]
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// Stats are the allocations made while running some code. `peak` is the most heap it
+/// used beyond what was allocated when it started.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub allocations: u64,
+    pub bytes: u64,
+    pub peak: u64,
+}
+
+/// measure runs `f` and returns its result with the allocations it made. Allocations
+/// on other threads at the same time are counted too.
+pub fn measure<T, F: FnOnce() -> T>(f: F) -> (T, Stats) {
+    let allocations = ALLOCATIONS.load(Ordering::SeqCst);
+    let bytes = BYTES.load(Ordering::SeqCst);
+    let start = CURRENT.load(Ordering::SeqCst);
+    PEAK.store(start, Ordering::SeqCst);
+    let result = f();
+    let stats = Stats{
+        allocations: ALLOCATIONS.load(Ordering::SeqCst) - allocations,
+        bytes: BYTES.load(Ordering::SeqCst) - bytes,
+        peak: PEAK.load(Ordering::SeqCst).saturating_sub(start),
+    };
+    (result, stats)
+}
+
+/// Profile is the allocation stats for parsing the input and for each part of a day.
+#[derive(Debug, Clone)]
+pub struct Profile {
+    pub parse: Stats,
+    pub parts: Vec<(u32, Stats)>,
+}
+
+/// profile counts the allocations made parsing `input` and then solving each
+/// implemented part of a solution. If part 2 needs part 1, the answer to part 1 is
+/// worked out beforehand and not counted.
+pub fn profile<S: Solution>(input: &str) -> Result<Profile, ParseError> {
+    let (parsed, parse) = measure(|| S::parse(input));
+    let parsed = parsed?;
+    let part1 = if S::PART2_NEEDS_PART1 {S::part1(&parsed).ok()} else {None};
+    let parts = S::PARTS.iter()
+        .map(|&part| {
+            let (_, stats) = if part == 1 {
+                measure(|| black_box(S::part1(&parsed)))
+            } else {
+                measure(|| black_box(S::part2(&parsed, part1.as_ref())))
+            };
+            (part, stats)
+        })
+        .collect();
+    Ok(Profile{parse, parts})
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measure_test() {
+        let (v, stats) = measure(|| {
+            let mut v: Vec<u64> = Vec::with_capacity(16);
+            v.extend(0..16);
+            let scratch = vec![0u8; 1024];
+            drop(scratch);
+            v
+        });
+        assert_eq!(v.len(), 16);
+        if ENABLED {
+            // Other tests allocate on their own threads at the same time, so these are
+            // only lower bounds.
+            assert!(stats.allocations >= 2);
+            assert!(stats.bytes >= 128 + 1024);
+            assert!(stats.peak >= 128 + 1024);
+        } else {
+            assert_eq!(stats, Stats::default());
+        }
+    }
+}
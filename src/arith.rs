@@ -0,0 +1,116 @@
+use crate::error::SolveError;
+use std::{fmt, ops::{Add, Mul, Sub}};
+
+/// Whether the operations here are checked. The steps of a solution whose numbers can
+/// grow without bound, such as counts of paths, go through them. With the
+/// `checked-arith` feature a result which does not fit its type is a
+/// `SolveError::Overflow` naming the day and step, rather than a panic in a debug build
+/// or a quietly wrong answer in a release build. Without it they are the plain operators.
+pub const CHECKED: bool = cfg!(feature = "checked-arith");
+
+/// Int is an integer type the operations here work on.
+pub trait Int: Copy + PartialOrd + fmt::Display + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> {
+    const NAME: &'static str;
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! int {
+    ($($t:ty),*) => {
+        $(impl Int for $t {
+            const NAME: &'static str = stringify!($t);
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+
+            fn checked_sub(self, other: Self) -> Option<Self> {
+                <$t>::checked_sub(self, other)
+            }
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$t>::checked_mul(self, other)
+            }
+        })*
+    };
+}
+
+int!(i32, i64, u32, u64, usize);
+
+/// Step is where in a solution a number is worked out, for the error if it overflows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub day: u32,
+    pub what: &'static str,
+}
+
+impl Step {
+    fn overflow<T: Int>(self, a: T, op: &str, b: T) -> SolveError {
+        SolveError::Overflow(format!("day {}, {}: {} {} {} does not fit in {}", self.day, self.what, a, op, b, T::NAME))
+    }
+}
+
+macro_rules! op {
+    ($(#[$doc:meta])* $name:ident, $checked:ident, $op:tt) => {
+        $(#[$doc])*
+        pub fn $name<T: Int>(a: T, b: T, step: Step) -> Result<T, SolveError> {
+            if CHECKED {
+                a.$checked(b).ok_or_else(|| step.overflow(a, stringify!($op), b))
+            } else {
+                Ok(a $op b)
+            }
+        }
+    };
+}
+
+op!(/// add returns `a + b`.
+    add, checked_add, +);
+op!(/// sub returns `a - b`.
+    sub, checked_sub, -);
+op!(/// mul returns `a * b`.
+    mul, checked_mul, *);
+
+/// abs returns the absolute value of `a`.
+pub fn abs<T: Int>(a: T, step: Step) -> Result<T, SolveError> {
+    if a < T::ZERO {sub(T::ZERO, a, step)} else {Ok(a)}
+}
+
+/// sum adds up numbers.
+pub fn sum<T: Int, I: IntoIterator<Item = T>>(items: I, step: Step) -> Result<T, SolveError> {
+    items.into_iter().try_fold(T::ZERO, |total, n| add(total, n, step))
+}
+
+/// product multiplies numbers together.
+pub fn product<T: Int, I: IntoIterator<Item = T>>(items: I, step: Step) -> Result<T, SolveError> {
+    items.into_iter().try_fold(T::ONE, |total, n| mul(total, n, step))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STEP: Step = Step{day: 3, what: "multiplying the trees"};
+
+    #[test]
+    fn op_test() {
+        assert_eq!(mul(6i64, 7, STEP), Ok(42));
+        assert_eq!(abs(-5i32, STEP), Ok(5));
+        assert_eq!(sum(vec![1usize, 2, 3], STEP), Ok(6));
+        assert_eq!(product(Vec::<u64>::new(), STEP), Ok(1));
+    }
+
+    #[cfg(feature = "checked-arith")]
+    #[test]
+    fn overflow_test() {
+        let err = SolveError::Overflow("day 3, multiplying the trees: 4294967296 * 4294967296 does not fit in u64".to_owned());
+        assert_eq!(product(vec![1u64 << 32, 1 << 32], STEP), Err(err));
+        assert!(abs(i32::MIN, STEP).is_err());
+        assert!(sub(0usize, 1, STEP).is_err());
+    }
+}
@@ -0,0 +1,96 @@
+use crate::error::ParseError;
+use crate::solution::Solution;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Options controls how many times each phase is run. Warmup runs are not measured.
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+    pub warmup: usize,
+    pub iterations: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options{warmup: 1, iterations: 10}
+    }
+}
+
+/// Stats summarises the measured run times of a phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Stats {
+        assert!(!samples.is_empty());
+        samples.sort_unstable();
+        let n = samples.len();
+        let median = if n % 2 == 1 {
+            samples[n / 2]
+        } else {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        };
+        Stats{min: samples[0], median, max: samples[n - 1]}
+    }
+}
+
+/// Timings are the stats for parsing the input and for each part of a day.
+#[derive(Debug, Clone)]
+pub struct Timings {
+    pub parse: Stats,
+    pub parts: Vec<(u32, Stats)>,
+}
+
+/// time runs `f` the number of times given by `opts` and returns the stats of the
+/// measured runs.
+pub fn time<T, F: FnMut() -> T>(opts: &Options, mut f: F) -> Stats {
+    for _ in 0..opts.warmup {
+        black_box(f());
+    }
+    let samples = (0..opts.iterations.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+    Stats::from_samples(samples)
+}
+
+/// bench times parsing `input` and then each implemented part of a solution. If part 2
+/// needs part 1, the answer to part 1 is worked out once beforehand.
+pub fn bench<S: Solution>(input: &str, opts: &Options) -> Result<Timings, ParseError> {
+    let parsed = S::parse(input)?;
+    let parse = time(opts, || S::parse(black_box(input)));
+    let part1 = if S::PART2_NEEDS_PART1 {S::part1(&parsed).ok()} else {None};
+    let parts = S::PARTS.iter()
+        .map(|&part| {
+            let stats = if part == 1 {
+                time(opts, || S::part1(black_box(&parsed)))
+            } else {
+                time(opts, || S::part2(black_box(&parsed), part1.as_ref()))
+            };
+            (part, stats)
+        })
+        .collect();
+    Ok(Timings{parse, parts})
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_test() {
+        let ms = Duration::from_millis;
+        let stats = Stats::from_samples(vec![ms(5), ms(1), ms(3)]);
+        assert_eq!(stats, Stats{min: ms(1), median: ms(3), max: ms(5)});
+
+        let stats = Stats::from_samples(vec![ms(4), ms(1), ms(2), ms(8)]);
+        assert_eq!(stats.median, ms(3));
+    }
+}
@@ -0,0 +1,116 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// The server puzzle inputs are fetched from when none is configured.
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Config is how to talk to the puzzle server. It is read from `aoc2020/config.toml` in
+/// the user's config directory (or the file named by `AOC2020_CONFIG`), e.g.
+///
+/// ```toml
+/// session = "53616c7465645f5f..."
+/// base_url = "https://adventofcode.com"
+/// cache_dir = "/home/me/.cache/aoc2020"
+/// history = "/home/me/.local/share/aoc2020/history.csv"
+/// ```
+///
+/// Each setting can be overridden by an environment variable: `AOC2020_SESSION`,
+/// `AOC2020_BASE_URL`, `AOC2020_CACHE` and `AOC2020_HISTORY`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// The value of the `session` cookie of a browser logged in to the server.
+    pub session: Option<String>,
+    pub base_url: String,
+    /// Where fetched inputs are kept so that they are only downloaded once.
+    pub cache_dir: PathBuf,
+    /// The file recording every answer submitted.
+    pub history: PathBuf,
+}
+
+impl Config {
+    /// load reads the config file, if there is one, and the environment.
+    pub fn load() -> Result<Config, String> {
+        let path = match env::var_os("AOC2020_CONFIG") {
+            Some(path) => Some(PathBuf::from(path)),
+            None => dirs::config_dir().map(|dir| dir.join("aoc2020").join("config.toml")),
+        };
+        let text = match &path {
+            Some(path) if path.exists() => {
+                Some(fs::read_to_string(path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?)
+            },
+            _ => None,
+        };
+        let source = path.map(|p| p.display().to_string()).unwrap_or_default();
+        Config::from_sources(text.as_deref().map(|t| (source.as_str(), t)), |name| env::var(name).ok())
+    }
+
+    /// from_sources builds a config from the contents of a config file, labelled with
+    /// where it came from, and the environment variables looked up by `env`.
+    pub fn from_sources<F>(file: Option<(&str, &str)>, env: F) -> Result<Config, String>
+        where F: Fn(&str) -> Option<String>
+    {
+        let table = match file {
+            Some((source, text)) => text.parse::<toml::Table>().map_err(|e| format!("{}: {}", source, e))?,
+            None => toml::Table::new(),
+        };
+        let setting = |key: &str, var: &str| -> Result<Option<String>, String> {
+            if let Some(value) = env(var).filter(|v| !v.is_empty()) {
+                return Ok(Some(value));
+            }
+            match table.get(key) {
+                None => Ok(None),
+                Some(toml::Value::String(s)) => Ok(Some(s.clone())),
+                Some(_) => Err(format!("{}: `{}` must be a string", file.map_or("config", |(s, _)| s), key)),
+            }
+        };
+        let session = setting("session", "AOC2020_SESSION")?;
+        let base_url = setting("base_url", "AOC2020_BASE_URL")?
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_owned())
+            .trim_end_matches('/')
+            .to_owned();
+        let cache_dir = match setting("cache_dir", "AOC2020_CACHE")? {
+            Some(dir) => PathBuf::from(dir),
+            None => dirs::cache_dir().unwrap_or_else(env::temp_dir).join("aoc2020"),
+        };
+        let history = match setting("history", "AOC2020_HISTORY")? {
+            Some(path) => PathBuf::from(path),
+            None => dirs::data_local_dir().unwrap_or_else(env::temp_dir).join("aoc2020").join("history.csv"),
+        };
+        Ok(Config{session, base_url, cache_dir, history})
+    }
+
+    /// session returns the session cookie, or an error saying how to set it.
+    pub fn session(&self) -> Result<&str, String> {
+        self.session.as_deref().ok_or_else(|| {
+            "no session cookie: set AOC2020_SESSION or `session` in the config file".to_owned()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_sources_test() {
+        let no_env = |_: &str| None;
+        let config = Config::from_sources(None, no_env).unwrap();
+        assert_eq!((config.session, config.base_url.as_str()), (None, DEFAULT_BASE_URL));
+
+        let file = "session = \"abc\"\nbase_url = \"http://localhost:8080/\"\ncache_dir = \"/tmp/c\"\n";
+        let config = Config::from_sources(Some(("config.toml", file)), no_env).unwrap();
+        assert_eq!(config.session(), Ok("abc"));
+        assert_eq!(config.base_url, "http://localhost:8080");
+        assert_eq!(config.cache_dir, PathBuf::from("/tmp/c"));
+        assert!(config.history.ends_with("aoc2020/history.csv"));
+
+        let env = |name: &str| if name == "AOC2020_SESSION" {Some("def".to_owned())} else {None};
+        let config = Config::from_sources(Some(("config.toml", file)), env).unwrap();
+        assert_eq!(config.session(), Ok("def"));
+
+        let err = Config::from_sources(Some(("config.toml", "session = 1\n")), no_env).unwrap_err();
+        assert_eq!(err, "config.toml: `session` must be a string");
+        assert!(Config::from_sources(Some(("config.toml", "session = \n")), no_env).is_err());
+    }
+}
@@ -0,0 +1,87 @@
+use crate::{Answer, ParseError, Solution, SolveError};
+use crate::parse::parse_lines;
+use crate::gen::Rng;
+use std::collections::HashSet;
+
+fn part1(numbers: &[i64]) -> Option<i64> {
+    for i in 0..numbers.len() {
+        for j in (i+1)..numbers.len(){
+            let n0 = numbers[i];
+            let n1 = numbers[j];
+            if n0 + n1 == 2020 {
+                return Some(n0 * n1);
+            }
+        }
+    }
+    None
+}
+
+fn part2(numbers: &[i64]) -> Option<i64> {
+    for i in 0..numbers.len() {
+        for j in (i+1)..numbers.len(){
+            for k in (j+1)..numbers.len() {
+                let n0 = numbers[i];
+                let n1 = numbers[j];
+                let n2 = numbers[k];
+                if n0 + n1 + n2 == 2020 {
+                    return Some(n0 * n1 * n2);
+                }
+            }
+        }
+    }
+    None
+}
+
+// generate plants one pair and one triple summing to 2020 among numbers of at least
+// 1011, which are too large to be part of any other pair or triple.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let planted = loop {
+        let a = rng.range(1, 1009);
+        let b = rng.range(1, 600);
+        let c = rng.range(1, 600);
+        let planted = vec![a, 2020 - a, b, c, 2020 - b - c];
+        let distinct: HashSet<_> = planted.iter().collect();
+        if distinct.len() == 5 && part1(&planted) == Some(a * (2020 - a)) && part2(&planted) == Some(b * c * (2020 - b - c)) {
+            break planted;
+        }
+    };
+    let mut numbers = planted.clone();
+    while numbers.len() < size.max(5) {
+        let n = rng.range(1011, 2019);
+        if !planted.iter().any(|&p| planted.contains(&(2020 - n - p)) || p == 2020 - n) {
+            numbers.push(n);
+        }
+    }
+    rng.shuffle(&mut numbers);
+    numbers.iter().map(|n| format!("{}\n", n)).collect()
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(input, |c| {
+            let n = c.number()?;
+            c.end()?;
+            Ok(n)
+        })
+    }
+
+    fn part1(numbers: &Self::Input) -> Result<Answer, SolveError> {
+        part1(numbers)
+            .map(Answer::from)
+            .ok_or_else(|| SolveError::NoSolution("no two entries sum to 2020".to_owned()))
+    }
+
+    fn part2(numbers: &Self::Input, _part1: Option<&Answer>) -> Result<Answer, SolveError> {
+        part2(numbers)
+            .map(Answer::from)
+            .ok_or_else(|| SolveError::NoSolution("no three entries sum to 2020".to_owned()))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String, String> {
+        Ok(generate(rng, size))
+    }
+}
@@ -0,0 +1,121 @@
+use crate::{Answer, ParseError, Solution, SolveError};
+use crate::parse::{parse_lines, Cursor};
+use crate::gen::Rng;
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct PasswordPolicy {
+    letter: char,
+    min: usize,
+    max: usize,
+    password: String,
+}
+
+// parse_password_policy parses a line such as "1-3 a: abcde".
+fn parse_password_policy(c: &mut Cursor) -> Result<PasswordPolicy, ParseError> {
+    let min = c.number()?;
+    c.literal("-")?;
+    let max = c.number()?;
+    c.literal(" ")?;
+    let letter = c.next_char(|c| c.is_ascii_lowercase(), "a letter")?;
+    c.literal(": ")?;
+    let password = c.word(|c| c.is_ascii_lowercase(), "a password")?.to_owned();
+    c.end()?;
+
+    Ok(PasswordPolicy{letter, min, max, password})
+}
+
+impl fmt::Display for PasswordPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{} {}: {}", self.min, self.max, self.letter, self.password)
+    }
+}
+
+fn xor(a: bool, b: bool) -> bool {
+    (a && !b) || (!a && b)
+}
+
+impl PasswordPolicy {
+    fn is_valid_p1(&self) -> bool {
+        let n = self.password.matches(self.letter).count();
+        n >= self.min && n <= self.max
+    }
+
+    // is_valid_p2 fails if a position is not in the password. Positions start at 1.
+    fn is_valid_p2(&self) -> Result<bool, SolveError> {
+        let letter_at = |pos: usize| pos.checked_sub(1)
+            .and_then(|i| self.password.chars().nth(i))
+            .ok_or_else(|| SolveError::MalformedInput(format!("no position {} in `{}`", pos, self)));
+        let c1 = letter_at(self.min)?;
+        let c2 = letter_at(self.max)?;
+        Ok(xor(self.letter == c1, self.letter == c2))
+    }
+}
+
+fn part1(passwords: &[PasswordPolicy]) -> i64 {
+    passwords.iter().map(|p| p.is_valid_p1())
+        .fold(0, |acc, valid| acc + (if valid {1} else {0}))
+}
+
+fn part2(passwords: &[PasswordPolicy]) -> Result<i64, SolveError> {
+    passwords.iter().map(|p| p.is_valid_p2())
+        .try_fold(0, |acc, valid| Ok(acc + (if valid? {1} else {0})))
+}
+
+fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size).map(|_| {
+        let letter = (b'a' + rng.below(26) as u8) as char;
+        let len = rng.range(1, 20) as usize;
+        let password = (0..len)
+            .map(|_| if rng.chance(0.3) {letter} else {(b'a' + rng.below(26) as u8) as char})
+            .collect();
+        let min = rng.range(1, len as i64) as usize;
+        let max = rng.range(min as i64, len as i64) as usize;
+        format!("{}\n", PasswordPolicy{letter, min, max, password})
+    }).collect()
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<PasswordPolicy>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(input, parse_password_policy)
+    }
+
+    fn part1(passwords: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(part1(passwords).into())
+    }
+
+    fn part2(passwords: &Self::Input, _part1: Option<&Answer>) -> Result<Answer, SolveError> {
+        part2(passwords).map(Answer::from)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String, String> {
+        Ok(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn display_round_trip_test(min in 0usize..100, max in 0usize..100, letter in "[a-z]", password in "[a-z]{1,20}") {
+            let policy = PasswordPolicy{letter: letter.chars().next().unwrap(), min, max, password};
+            prop_assert_eq!(parse_password_policy(&mut Cursor::new(&policy.to_string())), Ok(policy));
+        }
+    }
+
+    #[test]
+    fn position_out_of_range_test() {
+        let passwords = Day02::parse("1-3 a: abcde\n0-2 b: cdefg\n").unwrap();
+        assert_eq!(part2(&passwords[..1]), Ok(1));
+        assert_eq!(part2(&passwords), Err(SolveError::MalformedInput("no position 0 in `0-2 b: cdefg`".to_owned())));
+        let passwords = Day02::parse("2-9 c: ccccc\n").unwrap();
+        assert!(part2(&passwords).is_err());
+    }
+}
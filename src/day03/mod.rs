@@ -0,0 +1,105 @@
+use crate::{Answer, ParseError, Solution, SolveError};
+use crate::arith::{self, Step};
+use crate::gen::Rng;
+use crate::grid::{Grid, Pos};
+use crate::render::{self, Frames, Image};
+use std::convert::TryFrom;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Square {
+    Open,
+    Tree,
+}
+
+impl TryFrom<char> for Square {
+    type Error = &'static str;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '.' => Ok(Square::Open),
+            '#' => Ok(Square::Tree),
+            _ => Err("`.` or `#`"),
+        }
+    }
+}
+
+// traverse_grid counts the trees hit going down the slope from the top left. The map
+// repeats to the right, so it is addressed as a torus.
+fn traverse_grid(grid: &Grid<Square>, (dx, dy): Pos) -> i64 {
+    (0..)
+        .map(|i| (i * dx, i * dy))
+        .take_while(|&(_, y)| y < grid.height() as i64)
+        .filter(|&pos| grid.get_wrapped(pos) == &Square::Tree)
+        .count() as i64
+}
+
+const SLOPES: [Pos; 5] = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+
+fn part1(grid: &Grid<Square>) -> i64 {
+    traverse_grid(grid, (3, 1))
+}
+
+fn part2(grid: &Grid<Square>) -> Result<i64, SolveError> {
+    let step = Step{day: 3, what: "multiplying the trees hit on each slope"};
+    arith::product(SLOPES.iter().map(|&slope| traverse_grid(grid, slope)), step)
+}
+
+// draw_path draws the map, repeated as far to the right as the toboggan goes, with the
+// squares it passes through marked.
+fn draw_path(grid: &Grid<Square>, (dx, dy): Pos) -> Image {
+    let height = grid.height() as i64;
+    let width = ((height - 1) / dy * dx + 1).max(grid.width() as i64);
+    let mut image = Image::new(width as usize, height as usize, render::WHITE);
+    for (x, y) in (0..width).flat_map(|x| (0..height).map(move |y| (x, y))) {
+        if grid.get_wrapped((x, y)) == &Square::Tree {
+            image.set(x, y, render::GREEN);
+        }
+    }
+    for (x, y) in (0..).map(|i| (i * dx, i * dy)).take_while(|&(_, y)| y < height) {
+        let hit = grid.get_wrapped((x, y)) == &Square::Tree;
+        image.set(x, y, if hit {render::RED} else {render::BLUE});
+    }
+    image.scaled(2)
+}
+
+fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size).map(|i| {
+        let row: String = (0..31)
+            .map(|j| if (i, j) != (0, 0) && rng.chance(0.2) {'#'} else {'.'})
+            .collect();
+        row + "\n"
+    }).collect()
+}
+
+fn render(grid: &Grid<Square>, frames: &mut Frames) {
+    for &(dx, dy) in SLOPES.iter() {
+        frames.push(&format!("slope-{}-{}", dx, dy), draw_path(grid, (dx, dy)));
+    }
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Grid<Square>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Grid::parse(input)
+    }
+
+    fn part1(grid: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(part1(grid).into())
+    }
+
+    fn part2(grid: &Self::Input, _part1: Option<&Answer>) -> Result<Answer, SolveError> {
+        part2(grid).map(Answer::from)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String, String> {
+        Ok(generate(rng, size))
+    }
+
+    fn render(grid: &Self::Input, frames: &mut Frames) -> Result<(), String> {
+        render(grid, frames);
+        Ok(())
+    }
+}
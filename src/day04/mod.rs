@@ -0,0 +1,211 @@
+use crate::{Answer, ParseError, Solution, SolveError};
+use crate::parse::{records, Cursor};
+use crate::gen::Rng;
+use std::collections::HashMap;
+
+const EYE_COLOURS: [&str; 7] = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+
+pub type Passport = HashMap<String, String>;
+
+fn part1(passports: &[Passport]) -> i64 {
+    passports.iter()
+        .map(|p| p.len() == 8 || (p.len() == 7 && !p.contains_key("cid")))    
+        .map(|b| if b {1} else {0})
+        .sum()
+}
+
+fn parse_byr(s: &str) -> Result<(), &'static str> {
+    let byear = s.parse::<usize>().or(Err("could not parse birth year as int"))?;
+    if !(1920..=2002).contains(&byear) {
+        return Err("birth year out of range")
+    }
+    Ok(())
+}
+
+fn parse_iyr(s: &str) -> Result<(), &'static str> {
+    let iyear = s.parse::<usize>().or(Err("could not parse issue year as int"))?;
+    if !(2010..=2020).contains(&iyear) {
+        return Err("issue year out of range")
+    }
+    Ok(())
+}
+
+fn parse_eyr(s: &str) -> Result<(), &'static str> {
+    let eyear = s.parse::<usize>().or(Err("could not parse expiration year as int"))?;
+    if !(2020..=2030).contains(&eyear) {
+        return Err("expiration year out of range")
+    }
+    Ok(())
+}
+
+fn parse_hgt(s: &str) -> Result<(), &'static str> {
+    let (height, range, err) = if let Some(cm) = s.strip_suffix("cm") {
+        (cm, 150..=193, "height cm out of range")
+    } else if let Some(inches) = s.strip_suffix("in") {
+        (inches, 59..=76, "height inches out of range")
+    } else {
+        return Err("invalid height units")
+    };
+    let height = height.parse::<usize>().or(Err("could not parse height as int"))?;
+    if !range.contains(&height) {
+        return Err(err)
+    }
+    Ok(())
+}
+
+fn parse_hcl(s: &str) -> Result<(), &'static str> {
+    if s.len() != 7 || !s.starts_with('#') {
+        return Err("invalid hair color")
+    }
+    i64::from_str_radix(&s[1..], 16).or(Err("invalid hair color"))?;
+    Ok(())
+}
+
+fn parse_ecl(s: &str) -> Result<(), &'static str> {
+    if !EYE_COLOURS.contains(&s) {
+        return Err("invalid eye color")
+    }
+    Ok(())
+}
+
+fn parse_pid(s: &str) -> Result<(), &'static str> {
+    if s.len() != 9 || !s.chars().all(|c| c.is_ascii_digit()) {
+        return Err("invalid passport id")
+    }
+    Ok(())
+}
+
+fn passport_is_valid(p: &Passport) -> Result<(), &'static str> {
+    p.get("byr").ok_or("byr missing").and_then(|s| parse_byr(s))?;
+    p.get("iyr").ok_or("iyr missing").and_then(|s| parse_iyr(s))?;
+    p.get("eyr").ok_or("eyr missing").and_then(|s| parse_eyr(s))?;
+    p.get("hgt").ok_or("hgt missing").and_then(|s| parse_hgt(s))?;
+    p.get("hcl").ok_or("hcl missing").and_then(|s| parse_hcl(s))?;
+    p.get("ecl").ok_or("ecl missing").and_then(|s| parse_ecl(s))?;
+    p.get("pid").ok_or("pid missing").and_then(|s| parse_pid(s))?;
+    Ok(())
+}
+
+fn count(vals: impl Iterator<Item = bool>) -> i64 {
+    vals.map(|b| if b {1} else {0}).sum()
+}
+
+fn part2(passports: &[Passport]) -> i64 {
+    let valid = passports.iter().map(|p| passport_is_valid(p).is_ok());
+    count(valid)
+}
+
+// parse_fields parses a line of space separated "key:value" fields into a passport.
+fn parse_fields(c: &mut Cursor, p: &mut Passport) -> Result<(), ParseError> {
+    loop {
+        let key = c.word(|c| c.is_ascii_lowercase(), "a field name")?;
+        c.literal(":")?;
+        let value = c.word(|c| !c.is_whitespace(), "a field value")?;
+        p.insert(key.to_owned(), value.to_owned());
+        if c.is_empty() {
+            return Ok(());
+        }
+        c.literal(" ")?;
+    }
+}
+
+// generate_field returns a value for a passport field which is valid most of the time.
+fn generate_field(rng: &mut Rng, key: &str) -> String {
+    let valid = rng.chance(0.8);
+    let year = |rng: &mut Rng, lo, hi| {
+        let year = if valid {rng.range(lo, hi)} else if rng.chance(0.5) {rng.range(lo - 20, lo - 1)} else {rng.range(hi + 1, hi + 20)};
+        year.to_string()
+    };
+    let digits = |rng: &mut Rng, n| (0..n).map(|_| rng.range(0, 9).to_string()).collect::<String>();
+    match key {
+        "byr" => year(rng, 1920, 2002),
+        "iyr" => year(rng, 2010, 2020),
+        "eyr" => year(rng, 2020, 2030),
+        "hgt" if valid && rng.chance(0.5) => format!("{}cm", rng.range(150, 193)),
+        "hgt" if valid => format!("{}in", rng.range(59, 76)),
+        "hgt" => match rng.below(3) {
+            0 => format!("{}cm", rng.range(100, 149)),
+            1 => format!("{}in", rng.range(77, 99)),
+            _ => rng.range(100, 200).to_string(),
+        },
+        "hcl" => {
+            let hex: String = (0..6).map(|_| *rng.choose(b"0123456789abcdef") as char).collect();
+            if valid {format!("#{}", hex)} else if rng.chance(0.5) {hex} else {format!("#{}", &hex[..3])}
+        },
+        "ecl" if valid => rng.choose(&EYE_COLOURS).to_string(),
+        "ecl" => rng.word(1) + "z",
+        "pid" if valid => digits(rng, 9),
+        "pid" => {
+            let n = if rng.chance(0.5) {8} else {10};
+            digits(rng, n)
+        },
+        _ => rng.range(100, 350).to_string(),
+    }
+}
+
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut passports = Vec::new();
+    for _ in 0..size {
+        let mut fields = Vec::new();
+        for key in ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"].iter() {
+            if rng.chance(if *key == "cid" {0.5} else {0.9}) {
+                fields.push(format!("{}:{}", key, generate_field(rng, key)));
+            }
+        }
+        rng.shuffle(&mut fields);
+        let mut passport = String::new();
+        for (i, field) in fields.iter().enumerate() {
+            if i > 0 {
+                passport.push(if rng.chance(0.3) {'\n'} else {' '});
+            }
+            passport.push_str(field);
+        }
+        if !passport.is_empty() {
+            passports.push(passport + "\n");
+        }
+    }
+    passports.join("\n")
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Vec<Passport>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        records(input)
+            .map(|record| {
+                let mut p = HashMap::new();
+                record.parse_lines(|c| parse_fields(c, &mut p))?;
+                Ok(p)
+            })
+            .collect()
+    }
+
+    fn part1(passports: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(part1(passports).into())
+    }
+
+    fn part2(passports: &Self::Input, _part1: Option<&Answer>) -> Result<Answer, SolveError> {
+        Ok(part2(passports).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String, String> {
+        Ok(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_hgt_test() {
+        assert_eq!(parse_hgt("60in"), Ok(()));
+        assert_eq!(parse_hgt("190cm"), Ok(()));
+        assert_eq!(parse_hgt("190in"), Err("height inches out of range"));
+        for hgt in ["5", "", "cm", "1é", "é0cm", "190"] {
+            assert!(parse_hgt(hgt).is_err(), "{}", hgt);
+        }
+    }
+}
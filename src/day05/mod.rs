@@ -0,0 +1,139 @@
+use crate::{Answer, ParseError, Solution, SolveError};
+use crate::parse::{parse_lines, Cursor};
+use crate::gen::Rng;
+
+#[derive(Debug)]
+enum Row {
+    Front,
+    Back
+}
+
+#[derive(Debug)]
+enum Column {
+    Left,
+    Right
+}
+
+#[derive(Debug)]
+pub struct Partition {
+    rows: Vec<Row>,
+    columns: Vec<Column>,
+}
+
+fn find_seat_row(p: &[Row]) -> usize {
+    assert!(p.len() == 7);
+    let res = p.iter().fold((0, 127), |(min, max), r| {
+        let mid = min + ((max - min) / 2);
+        match r {
+            Row::Front => (min, mid),
+            Row::Back => (mid+1, max),
+        }
+    });
+    assert!(res.0 == res.1);
+    res.0
+}
+
+fn find_seat_column(p: &[Column]) -> usize {
+    assert!(p.len() == 3);
+    let res = p.iter().fold((0, 7), |(min, max), r| {
+        let mid = min + ((max - min) / 2);
+        match r {
+            Column::Left => (min, mid),
+            Column::Right => (mid+1, max),
+        }
+    });
+    assert!(res.0 == res.1);
+    res.0
+}
+
+fn find_seat(p: &Partition) -> (usize, usize) {
+    (find_seat_row(&p.rows), find_seat_column(&p.columns))
+}
+
+fn seat_id((row, column): (usize, usize)) -> usize {
+    row * 8 + column
+}
+
+fn parse_partition(c: &mut Cursor) -> Result<Partition, ParseError> {
+    let mut rows = Vec::with_capacity(7);
+    for _ in 0..7 {
+        let row = match c.next_char(|c| c == 'F' || c == 'B', "`F` or `B`")? {
+            'F' => Row::Front,
+            _ => Row::Back,
+        };
+        rows.push(row);
+    }
+
+    let mut columns = Vec::with_capacity(3);
+    for _ in 0..3 {
+        let column = match c.next_char(|c| c == 'L' || c == 'R', "`L` or `R`")? {
+            'L' => Column::Left,
+            _ => Column::Right,
+        };
+        columns.push(column);
+    }
+    c.end()?;
+
+    Ok(Partition{rows, columns})
+}
+
+fn part1(partitions: &[Partition]) -> Option<usize> {
+    partitions.iter()
+        .map(find_seat)
+        .map(seat_id)
+        .max()
+}
+
+fn part2(partitions: &[Partition]) -> Option<usize> {
+    let mut seat_ids: Vec<_> = partitions.iter()
+        .map(find_seat)
+        .map(seat_id)
+        .collect();
+    seat_ids.sort();
+    for i in 1..seat_ids.len() {
+        if seat_ids[i] - seat_ids[i-1] == 2 {
+            return Some(seat_ids[i-1] + 1);
+        }
+    }
+    None
+}
+
+// generate writes the boarding passes of a run of consecutive seats with one missing.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let n = size.clamp(3, 1000);
+    let start = rng.range(1, (1023 - n) as i64) as usize;
+    let missing = start + rng.range(1, n as i64 - 1) as usize;
+    let mut seats: Vec<_> = (start..=start + n).filter(|&id| id != missing).collect();
+    rng.shuffle(&mut seats);
+    seats.iter().map(|id| {
+        let row: String = (0..7).rev().map(|b| if id >> (b + 3) & 1 == 1 {'B'} else {'F'}).collect();
+        let column: String = (0..3).rev().map(|b| if id >> b & 1 == 1 {'R'} else {'L'}).collect();
+        format!("{}{}\n", row, column)
+    }).collect()
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Vec<Partition>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(input, parse_partition)
+    }
+
+    fn part1(partitions: &Self::Input) -> Result<Answer, SolveError> {
+        part1(partitions)
+            .map(Answer::from)
+            .ok_or_else(|| SolveError::NoSolution("there are no boarding passes".to_owned()))
+    }
+
+    fn part2(partitions: &Self::Input, _part1: Option<&Answer>) -> Result<Answer, SolveError> {
+        part2(partitions)
+            .map(Answer::from)
+            .ok_or_else(|| SolveError::NoSolution("no seat is missing between two others".to_owned()))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String, String> {
+        Ok(generate(rng, size))
+    }
+}
@@ -0,0 +1,69 @@
+use crate::{Answer, ParseError, Solution, SolveError};
+use crate::parse::records;
+use crate::gen::Rng;
+use std::collections::{HashSet};
+
+fn part1(groups: &[Vec<String>]) -> usize {
+    groups.iter()
+        .map(|g| {
+            g.iter()
+                .flat_map(|s| s.chars())
+                .collect::<HashSet<_>>()
+                .len()
+        })
+    .sum()
+}
+
+fn part2(groups: &[Vec<String>]) -> usize {
+    groups.iter()
+        .map(|g| {
+            g.iter()
+                .map(|s| s.chars().collect::<HashSet<_>>())
+                .reduce(|acc, set| acc.intersection(&set).cloned().collect())
+                .map_or(0, |set| set.len())
+        })
+        .sum()
+}
+
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let groups: Vec<String> = (0..size).map(|_| {
+        let common: Vec<char> = ('a'..='z').filter(|_| rng.chance(0.4)).collect();
+        (0..rng.range(1, 5)).map(|_| {
+            let mut answers: String = common.iter().filter(|_| rng.chance(0.7)).collect();
+            if answers.is_empty() {
+                answers.push((b'a' + rng.below(26) as u8) as char);
+            }
+            answers + "\n"
+        }).collect()
+    }).collect();
+    groups.join("\n")
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = Vec<Vec<String>>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        records(input)
+            .map(|group| group.parse_lines(|c| {
+                // Each line holds the questions (a to z) one person answered "yes" to.
+                let answers = c.word(|c| c.is_ascii_lowercase(), "a question letter")?;
+                c.end()?;
+                Ok(answers.to_owned())
+            }))
+            .collect()
+    }
+
+    fn part1(groups: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(part1(groups).into())
+    }
+
+    fn part2(groups: &Self::Input, _part1: Option<&Answer>) -> Result<Answer, SolveError> {
+        Ok(part2(groups).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String, String> {
+        Ok(generate(rng, size))
+    }
+}
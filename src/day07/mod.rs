@@ -0,0 +1,260 @@
+use crate::{Answer, ParseError, Solution, SolveError};
+use crate::parse::parse_lines;
+use crate::gen::Rng;
+use lazy_static::lazy_static;
+use log::{debug, trace};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use regex::Regex;
+
+#[derive(Debug, PartialEq, Eq)]
+struct Bag {
+    colour: String,
+    contents: HashMap<String, usize>
+}
+
+#[derive(Debug)]
+pub struct Rules {
+    bags: Vec<Bag>,
+    lookup: HashMap<String, usize>
+}
+
+impl Rules {
+    fn new() -> Rules {
+        Rules{bags: Vec::new(), lookup: HashMap::new()}
+    }
+
+    fn get_bag(&self, colour: &str) -> Option<&Bag> {
+        let i = self.lookup.get(colour)?;
+        self.bags.get(i.to_owned())
+    }
+
+    // bag is get_bag for a colour named in a rule, which must have a rule of its own.
+    fn bag(&self, colour: &str) -> Result<&Bag, SolveError> {
+        self.get_bag(colour)
+            .ok_or_else(|| SolveError::MalformedInput(format!("there is no rule for {} bags", colour)))
+    }
+
+    fn insert_bag(&mut self, bag: Bag) {
+        match self.lookup.get(&bag.colour) {
+            None => {
+                self.lookup.insert(bag.colour.clone(), self.bags.len());
+                self.bags.push(bag);
+            }
+            Some(&i) => {
+                self.bags[i] = bag;
+            }
+        }
+    }
+}
+
+// Bag is displayed as the rule it was parsed from, with its contents sorted by colour.
+impl fmt::Display for Bag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} bags contain ", self.colour)?;
+        if self.contents.is_empty() {
+            return write!(f, "no other bags.");
+        }
+        let mut contents: Vec<_> = self.contents.iter().collect();
+        contents.sort();
+        for (i, (colour, n)) in contents.into_iter().enumerate() {
+            let sep = if i == 0 {""} else {", "};
+            let plural = if *n == 1 {""} else {"s"};
+            write!(f, "{}{} {} bag{}", sep, n, colour, plural)?;
+        }
+        write!(f, ".")
+    }
+}
+
+fn parse_bag(s: &str) -> Result<Bag, ParseError> {
+    lazy_static!{
+        static ref RE1: Regex = Regex::new(r"^([a-z ]+) bags contain (.+)$").unwrap();
+    }
+    let caps1 = RE1.captures(s)
+        .ok_or_else(|| ParseError::at(s, 1, "a rule like `<colour> bags contain <contents>.`"))?;
+
+    let colour = caps1[1].to_owned();
+    let contents_m = caps1.get(2).unwrap();
+
+    let contents_s = contents_m.as_str().strip_suffix('.')
+        .ok_or_else(|| ParseError::at(s, s.len() + 1, "`.`"))?;
+    if contents_s == "no other bags" {
+        return Ok(Bag{colour, contents: HashMap::new()})
+    }
+
+    lazy_static!{
+        static ref RE2: Regex = Regex::new(r"^(\d+) ([a-z ]+) bags?$").unwrap();
+    }
+    let mut contents = HashMap::new();
+    let mut offset = contents_m.start();
+    for item in contents_s.split(", ") {
+        let cap = RE2.captures(item)
+            .ok_or_else(|| ParseError::at(s, offset + 1, "`<count> <colour> bags`"))?;
+        let n = cap[1].parse::<usize>()
+            .map_err(|_| ParseError::at(s, offset + 1, "a count").found(format!("`{}` (out of range)", &cap[1])))?;
+        contents.insert(cap[2].to_owned(), n);
+        offset += item.len() + 2;
+    }
+
+    Ok(Bag{colour, contents})
+}
+
+fn parse_rules(input: &str) -> Result<Rules, ParseError> {
+    let mut rules = Rules::new();
+    let bags = parse_lines(input, |c| parse_bag(c.text()))?;
+    for bag in bags.into_iter() {
+        rules.insert_bag(bag);
+    }
+    Ok(rules)
+}
+
+fn part1(rules: &Rules) -> Result<usize, SolveError> {
+    // Depth-first search to find paths leading to the shiny gold bag.
+    // We maintain a cache so as not to re-traverse the same paths.
+    // If cache[colour] == true, then there is at least one path starting at that
+    // bag to the shiny gold bag.
+    let mut cache: HashMap<String, bool> = HashMap::new();
+    for bag in rules.bags.iter().filter(|b| b.colour != "shiny gold") {
+        let mut stack = vec![&bag.colour];
+        while !stack.is_empty() {
+            trace!(stack:? = stack; "visit");
+            let parent = rules.bag(stack[stack.len() - 1])?;
+            let child_colour = parent.contents
+                .keys().find(|k| !cache.contains_key(k.as_str()));
+            match child_colour {
+                Some(child_colour) => {
+                    let child = rules.bag(child_colour)?;
+                    if stack.contains(&child_colour) {
+                        return Err(SolveError::Cycle(format!("{} bags contain themselves", child_colour)));
+                    }
+                    if child.colour == "shiny gold" {
+                        debug!(colour = parent.colour.as_str(), leads_to_gold = true; "cached");
+                        cache.insert(parent.colour.clone(), true);
+                        stack.pop();
+                    } else {
+                        stack.push(child_colour);
+                    }
+                }
+                None => {
+                    // We've visited all of this bag's children (or it has no children).
+                    // Check if any child leads to shiny gold
+                    let c = parent.contents.keys().filter(|k| cache[k.as_str()]).count();
+                    debug!(colour = parent.colour.as_str(), leads_to_gold = c > 0; "cached");
+                    cache.insert(parent.colour.clone(), c > 0);
+                    stack.pop();
+                }
+            }
+        }
+    }
+
+    Ok(cache.values().map(|b| if *b {1} else {0}).sum())
+}
+
+// count_inside counts the bags inside the last bag on `path`, which holds the colours
+// of the bags it is inside of, so that a bag inside itself is noticed.
+fn count_inside<'a>(rules: &'a Rules, path: &mut Vec<&'a str>) -> Result<usize, SolveError> {
+    let bag = rules.bag(path[path.len() - 1])?;
+    let mut count = 0;
+    for (colour, n) in bag.contents.iter() {
+        if path.contains(&colour.as_str()) {
+            return Err(SolveError::Cycle(format!("{} bags contain themselves", colour)));
+        }
+        path.push(colour);
+        count += n * (1 + count_inside(rules, path)?);
+        path.pop();
+    }
+    Ok(count)
+}
+
+fn part2(rules: &Rules) -> Result<usize, SolveError> {
+    if rules.get_bag("shiny gold").is_none() {
+        return Err(SolveError::NoSolution("there is no rule for shiny gold bags".to_owned()));
+    }
+    count_inside(rules, &mut vec!["shiny gold"])
+}
+
+// generate writes the rules of a random DAG of bags: each bag only contains bags which
+// come after it. The shiny gold bag is placed near the end so that part 2's count
+// stays small.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let n = size.max(2);
+    let mut colours = HashSet::new();
+    while colours.len() < n - 1 {
+        colours.insert(format!("{} {}", rng.word(2), rng.word(2)));
+    }
+    let mut colours: Vec<_> = colours.into_iter().collect();
+    colours.sort();
+    rng.shuffle(&mut colours);
+    colours.insert(n.saturating_sub(12), "shiny gold".to_owned());
+
+    let mut bags: Vec<_> = (0..n).map(|i| {
+        let mut contents = HashMap::new();
+        let later = n - i - 1;
+        let count = if colours[i] == "shiny gold" {1 + rng.below(3)} else {rng.below(4)};
+        for _ in 0..count.min(later) {
+            let j = i + 1 + rng.below(later.min(20));
+            contents.insert(colours[j].clone(), rng.range(1, 5) as usize);
+        }
+        Bag{colour: colours[i].clone(), contents}
+    }).collect();
+    rng.shuffle(&mut bags);
+    bags.iter().map(|bag| format!("{}\n", bag)).collect()
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Rules;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_rules(input)
+    }
+
+    fn part1(rules: &Self::Input) -> Result<Answer, SolveError> {
+        part1(rules).map(Answer::from)
+    }
+
+    fn part2(rules: &Self::Input, _part1: Option<&Answer>) -> Result<Answer, SolveError> {
+        part2(rules).map(Answer::from)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String, String> {
+        Ok(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn colour() -> impl Strategy<Value = String> {
+        "[a-z]{1,8} [a-z]{1,8}".prop_filter("not a keyword", |c| {
+            !c.split(' ').any(|w| ["bag", "bags", "contain", "no", "other"].contains(&w))
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn display_round_trip_test(colour in colour(), contents in prop::collection::hash_map(colour(), 1usize..100, 0..4)) {
+            let bag = Bag{colour, contents};
+            prop_assert_eq!(parse_bag(&bag.to_string()), Ok(bag));
+        }
+    }
+
+    #[test]
+    fn malformed_rules_test() {
+        let rules = parse_rules("shiny gold bags contain 2 dark red bags.\n").unwrap();
+        let err = SolveError::MalformedInput("there is no rule for dark red bags".to_owned());
+        assert_eq!(part2(&rules), Err(err));
+
+        let rules = parse_rules("shiny gold bags contain 1 dark red bag.\n\
+            dark red bags contain 1 bright blue bag.\n\
+            bright blue bags contain 3 dark red bags.\n").unwrap();
+        assert!(matches!(part1(&rules), Err(SolveError::Cycle(_))));
+        assert_eq!(part2(&rules), Err(SolveError::Cycle("dark red bags contain themselves".to_owned())));
+
+        let rules = parse_rules("dark red bags contain no other bags.\n").unwrap();
+        assert!(matches!(part2(&rules), Err(SolveError::NoSolution(_))));
+    }
+}
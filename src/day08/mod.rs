@@ -0,0 +1,237 @@
+use crate::{Answer, ParseError, Solution, SolveError};
+use crate::parse::{parse_lines, Cursor};
+use crate::gen::Rng;
+use log::{debug, trace};
+use std::collections::{HashSet};
+use std::convert::{TryFrom};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Instruction {
+    Acc(i64),
+    Jmp(i64),
+    Nop(i64),
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Instruction::Acc(n) => write!(f, "acc {:+}", n),
+            Instruction::Jmp(n) => write!(f, "jmp {:+}", n),
+            Instruction::Nop(n) => write!(f, "nop {:+}", n),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct VM {
+    instructions: Vec<Instruction>,
+    acc:          i64,
+    pos:          usize,
+}
+
+impl VM {
+    fn new(instructions: Vec<Instruction>) -> VM {
+        VM{instructions, acc: 0, pos: 0}
+    }
+
+    // execute runs the next instruction and returns whether it did. It does not if the
+    // program has already terminated or the instruction would jump outside of it. The
+    // program terminates when it moves to just past its last instruction. An `acc`
+    // which overflows the accumulator is an error.
+    fn execute(&mut self) -> Result<bool, SolveError> {
+        let instruction = match self.instructions.get(self.pos) {
+            Some(instruction) => instruction,
+            None => return Ok(false),
+        };
+        trace!(pc = self.pos, acc = self.acc, instruction:% = instruction; "execute");
+        match instruction {
+            Instruction::Acc(n) => {
+                self.acc = self.acc.checked_add(*n).ok_or_else(|| SolveError::Overflow(
+                    format!("day 8, running `{}` at {}: {} + {} does not fit in i64", instruction, self.pos, self.acc, n)))?;
+                self.pos += 1;
+            },
+            Instruction::Jmp(n) => {
+                let npos = i64::try_from(self.pos).ok()
+                    .and_then(|pos| pos.checked_add(*n))
+                    .and_then(|npos| usize::try_from(npos).ok())
+                    .filter(|&npos| npos <= self.instructions.len());
+                match npos {
+                    Some(npos) => self.pos = npos,
+                    None => return Ok(false),
+                }
+            },
+            Instruction::Nop(_) => {
+                self.pos += 1;
+            }
+        }
+        Ok(true)
+    }
+}
+
+fn parse_op(c: &mut Cursor) -> Result<Instruction, ParseError> {
+    let err = c.error("one of `acc`, `jmp` or `nop`");
+    let op = c.word(|c| c.is_ascii_lowercase(), "an operation")?;
+    c.literal(" ")?;
+    let n = c.number::<i64>()?;
+    c.end()?;
+    match op {
+        "acc" => Ok(Instruction::Acc(n)),
+        "jmp" => Ok(Instruction::Jmp(n)),
+        "nop" => Ok(Instruction::Nop(n)),
+        _ => Err(err),
+    }
+}
+
+fn parse_input(s: &str) -> Result<Vec<Instruction>, ParseError> {
+    parse_lines(s, parse_op)
+}
+
+fn part1(instructions: &[Instruction]) -> Result<i64, SolveError> {
+    let mut vm = VM::new(instructions.to_vec());
+    let mut visited: HashSet<usize> = HashSet::new();
+    loop {
+        if !visited.insert(vm.pos) {
+            debug!(pc = vm.pos, acc = vm.acc; "instruction run twice");
+            return Ok(vm.acc);
+        }
+        if !vm.execute()? {
+            return Err(SolveError::NoSolution(format!("the program stops at {} without looping", vm.pos)));
+        }
+    }
+}
+
+fn part2(instructions: &[Instruction]) -> Result<i64, SolveError> {
+    let swaps = instructions.iter()
+        .enumerate()
+        .filter_map(|(i, instruction)| {
+            match instruction {
+                Instruction::Acc(_) => None,
+                Instruction::Jmp(n) => Some((i, Instruction::Nop(*n))),
+                Instruction::Nop(n) => Some((i, Instruction::Jmp(*n))),
+            }
+        });
+    for (i, instruction) in swaps {
+        let mut new_instructions = instructions.to_vec();
+        debug!(pc = i, instruction:% = instruction; "trying repair");
+        new_instructions[i] = instruction;
+        if let Some(acc) = run_to_end(new_instructions)? {
+            debug!(pc = i, acc; "program terminates");
+            return Ok(acc);
+        }
+    }
+    Err(SolveError::NoSolution("no single repair makes the program terminate".to_owned()))
+}
+
+// run_to_end runs a program and returns the accumulator if it terminates, or None if
+// it loops or jumps outside of the program.
+fn run_to_end(instructions: Vec<Instruction>) -> Result<Option<i64>, SolveError> {
+    let mut vm = VM::new(instructions);
+    let mut visited: HashSet<usize> = HashSet::new();
+    while visited.insert(vm.pos) && vm.execute()? {}
+    Ok(if vm.pos == vm.instructions.len() {Some(vm.acc)} else {None})
+}
+
+// generate writes a program with one corrupted instruction, a `jmp` back to an earlier
+// instruction which should be a `nop`. All other jumps are forwards and none before
+// the corrupted instruction jump past it, so any other repair still reaches it and
+// loops: exactly one change fixes the program.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let n = size.max(2) as i64;
+    let corrupted = rng.range(n / 4, (3 * n / 4).max(n / 4));
+    let program: Vec<_> = (0..n).map(|i| {
+        // Jumps before the corrupted instruction must not pass it, the rest may jump
+        // to just after the end of the program.
+        let end = if i < corrupted {corrupted} else {n};
+        if i == corrupted {
+            return Instruction::Jmp(-rng.range(0, i));
+        }
+        match rng.below(3) {
+            0 => Instruction::Acc(rng.range(-50, 50)),
+            1 if i < end => Instruction::Jmp(rng.range(1, end - i)),
+            _ => Instruction::Nop(rng.range(-i, end - i)),
+        }
+    }).collect();
+    program.iter().map(|instruction| format!("{}\n", instruction)).collect()
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(instructions: &Self::Input) -> Result<Answer, SolveError> {
+        part1(instructions).map(Answer::from)
+    }
+
+    fn part2(instructions: &Self::Input, _part1: Option<&Answer>) -> Result<Answer, SolveError> {
+        part2(instructions).map(Answer::from)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String, String> {
+        Ok(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gen::Rng;
+    use proptest::prelude::*;
+
+    fn instruction() -> impl Strategy<Value = Instruction> {
+        prop_oneof![
+            any::<i64>().prop_map(Instruction::Acc),
+            any::<i64>().prop_map(Instruction::Jmp),
+            any::<i64>().prop_map(Instruction::Nop),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn display_round_trip_test(instruction in instruction()) {
+            prop_assert_eq!(parse_op(&mut Cursor::new(&instruction.to_string())), Ok(instruction));
+        }
+
+        #[test]
+        fn generate_test(seed in any::<u64>(), size in 2usize..50) {
+            let program = parse_input(&generate(&mut Rng::new(seed), size)).unwrap();
+            prop_assert_eq!(run_to_end(program.clone()), Ok(None));
+            let fixes = (0..program.len())
+                .filter(|&i| {
+                    let mut program = program.clone();
+                    program[i] = match program[i] {
+                        Instruction::Acc(_) => return false,
+                        Instruction::Jmp(n) => Instruction::Nop(n),
+                        Instruction::Nop(n) => Instruction::Jmp(n),
+                    };
+                    matches!(run_to_end(program), Ok(Some(_)))
+                })
+                .count();
+            prop_assert_eq!(fixes, 1);
+        }
+    }
+
+    #[test]
+    fn no_loop_test() {
+        let program = parse_input("acc +1\njmp -5\n").unwrap();
+        assert!(matches!(part1(&program), Err(SolveError::NoSolution(_))));
+        let program = parse_input("acc +1\njmp +9223372036854775807\n").unwrap();
+        assert!(matches!(part1(&program), Err(SolveError::NoSolution(_))));
+        let program = parse_input("acc +1\njmp +0\nacc +1\njmp -1\n").unwrap();
+        assert!(matches!(part2(&program), Err(SolveError::NoSolution(_))));
+    }
+
+    #[test]
+    fn overflow_test() {
+        let program = parse_input("acc +9223372036854775807\nacc +1\njmp -2\n").unwrap();
+        let err = SolveError::Overflow(
+            "day 8, running `acc +1` at 1: 9223372036854775807 + 1 does not fit in i64".to_owned());
+        assert_eq!(part1(&program), Err(err));
+        assert!(matches!(part2(&program), Err(SolveError::Overflow(_))));
+    }
+}
@@ -0,0 +1,163 @@
+use crate::{Answer, ParseError, Solution, SolveError};
+use crate::parse::parse_lines;
+use crate::gen::Rng;
+
+// Pairs implements an iterator over all distinct pairs of elements in a slice.
+struct Pairs<'a, T> {
+    items: &'a[T],
+    i: usize,
+    j: usize,
+}
+
+impl<'a, T> Pairs<'a, T> {
+    fn new(items: &'a[T]) -> Pairs<'a, T> {
+        Pairs{items, i: 0, j: 1}
+    }
+}
+
+impl<'a, T> Iterator for Pairs<'a, T> {
+    type Item = (&'a T, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let n = self.items.len();
+        if n < 2 {
+            return None;
+        }
+        if (self.i == n-2) && (self.j == n-1) {
+            return None;
+        }
+        let new_next = (&self.items[self.i], &self.items[self.j]);
+        if self.j == n-1 {
+            self.i += 1;
+            self.j = self.i + 1;
+        } else {
+            self.j += 1;
+        }
+        Some(new_next)
+    }
+}
+
+// The preamble length of the real inputs.
+const PREAMBLE: usize = 25;
+
+/// Xmas is the numbers sent and the length of the preamble, which each number after it
+/// must be the sum of two of.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Xmas {
+    pub preamble: usize,
+    pub numbers: Vec<usize>,
+}
+
+// parse_input parses one number per line. The preamble is 25 numbers long unless the
+// first line is `preamble: N`, as the puzzle's example, with a preamble of 5, needs.
+fn parse_input(s: &str) -> Result<Xmas, ParseError> {
+    let mut first = true;
+    let lines = parse_lines(s, |c| {
+        let preamble = first && c.rest().starts_with("preamble");
+        first = false;
+        if preamble {
+            c.literal("preamble: ")?;
+        }
+        let n = c.number()?;
+        c.end()?;
+        Ok((preamble, n))
+    })?;
+    let preamble = match lines.first() {
+        Some(&(true, n)) => n,
+        _ => PREAMBLE,
+    };
+    let numbers = lines.into_iter().filter(|&(p, _)| !p).map(|(_, n)| n).collect();
+    Ok(Xmas{preamble, numbers})
+}
+
+fn part1(numbers: &[usize], preamble: usize) -> Option<usize> {
+    for (i, &n) in numbers.iter().enumerate().skip(preamble) {
+        let prev = &numbers[i-preamble..i];
+        // A pair too large to add up cannot sum to n.
+        let m = Pairs::new(prev)
+            .filter_map(|(a, b)| a.checked_add(*b))
+            .find(|&x| n == x);
+        if m.is_none() {
+            return Some(n);
+        }
+    }
+    None
+}
+
+fn part2(numbers: &[usize], p1_ans: usize) -> Option<usize> {
+    let len = numbers.len();
+    for i in 0..len.saturating_sub(1) {
+        let mut sum = numbers[i];
+        for j in (i+1)..len {
+            sum += numbers[j];
+            if sum == p1_ans {
+                let slice = &numbers[i..=j];
+                return Some(slice.iter().min()? + slice.iter().max()?);
+            }
+        }
+    }
+    None
+}
+
+// generate writes a 25 number preamble followed by `size` numbers which are each the
+// sum of two of the 25 before them, except for one which is instead the sum of a
+// contiguous run of earlier numbers.
+fn generate(rng: &mut Rng, size: usize) -> Result<String, String> {
+    let size = size.max(1);
+    let mut numbers: Vec<usize> = (1..=50).collect();
+    rng.shuffle(&mut numbers);
+    numbers.truncate(25);
+    let invalid = 25 + size / 2 + rng.below(size - size / 2);
+    for i in 25..25 + size {
+        let prev = &numbers[i-25..i];
+        let n = if i == invalid {
+            loop {
+                let start = rng.below(i - 2);
+                let end = start + 2 + rng.below((i - start - 1).min(10));
+                let sum = numbers[start..end].iter().sum();
+                if Pairs::new(prev).all(|(a, b)| a + b != sum) {
+                    break sum;
+                }
+            }
+        } else {
+            let j = rng.below(25);
+            let k = (j + 1 + rng.below(24)) % 25;
+            prev[j].checked_add(prev[k])
+                .ok_or_else(|| format!("{} numbers would overflow, try a smaller size", size))?
+        };
+        numbers.push(n);
+    }
+    Ok(numbers.iter().map(|n| format!("{}\n", n)).collect())
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Xmas;
+
+    const PART2_NEEDS_PART1: bool = true;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(xmas: &Self::Input) -> Result<Answer, SolveError> {
+        part1(&xmas.numbers, xmas.preamble)
+            .map(Answer::from)
+            .ok_or_else(|| SolveError::NoSolution(
+                format!("every number is the sum of two of the {} before it", xmas.preamble)))
+    }
+
+    // Part 2 looks for a range summing to the part 1 answer.
+    fn part2(xmas: &Self::Input, part1: Option<&Answer>) -> Result<Answer, SolveError> {
+        let target = part1.and_then(Answer::as_int)
+            .ok_or_else(|| SolveError::NoSolution("part 1 has no answer".to_owned()))?;
+        part2(&xmas.numbers, target as usize)
+            .map(Answer::from)
+            .ok_or_else(|| SolveError::NoSolution(format!("no range of numbers sums to {}", target)))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String, String> {
+        generate(rng, size)
+    }
+}
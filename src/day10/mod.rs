@@ -0,0 +1,147 @@
+use crate::{Answer, ParseError, Solution, SolveError};
+use crate::arith::{self, Step};
+use crate::parse::parse_lines;
+use crate::gen::Rng;
+use std::{collections::{HashMap, HashSet}, iter};
+
+fn parse_input(s: &str) -> Result<Vec<usize>, ParseError> {
+    parse_lines(s, |c| {
+        let n = c.number()?;
+        c.end()?;
+        Ok(n)
+    })
+}
+
+// builtin returns the joltage of the device's built-in adapter, given the adapters
+// sorted by joltage.
+fn builtin(adapters: &[usize]) -> Result<usize, SolveError> {
+    adapters.last()
+        .map(|a| a + 3)
+        .ok_or_else(|| SolveError::MalformedInput("there are no adapters".to_owned()))
+}
+
+fn part1(mut adapters: Vec<usize>) -> Result<usize, SolveError> {
+    adapters.sort_unstable();    
+    let builtin = builtin(&adapters)?;
+
+    let iter1 = iter::once(&0).chain(adapters.iter());
+    let iter2 = adapters.iter().chain(iter::once(&builtin));
+
+    let (ones, threes) = iter1.zip(iter2)
+        .try_fold((0, 0), |(ones, threes), (a, b)| {
+            match b - a {
+                1 => Ok((ones + 1, threes)),
+                3 => Ok((ones, threes + 1)),
+                diff if diff > 3 => Err(SolveError::NoSolution(format!("nothing connects {} jolts to {} jolts", a, b))),
+                _ => Ok((ones, threes)),
+            }
+        })?;
+
+    Ok(ones * threes)
+}
+
+fn part2(mut adapters: Vec<usize>) -> Result<usize, SolveError> {
+    adapters.sort_unstable();
+    let builtin = builtin(&adapters)?;
+
+    let all_adapters: Vec<usize> = iter::once(0)
+        .chain(adapters)
+        .chain(iter::once(builtin))
+        .collect();
+    
+    // Construct a graph using a HashMap of parent -> children relationships. Each node
+    // in the graph corresponds to an adapter, and an edge exists from node i to node
+    // j if adapter j can connect to adapter i.
+    let mut graph = HashMap::<usize, HashSet<usize>>::new();
+    for (i, a) in all_adapters.iter().enumerate() {
+        let children = all_adapters[(i+1)..].iter()
+            .take(3)
+            .filter(|&b| b - a <= 3)
+            .cloned()
+            .collect();
+        graph.insert(*a, children);
+    }
+
+    // Count the total number of paths from the "outlet" to the "builtin" by walking
+    // backwards over the graph.
+    // counts stores the number of paths from each node to the "builtin" node
+    // Note: we can iterate in reverse here because all_adapters is already a topological
+    // ordering of the graph.
+    let step = Step{day: 10, what: "counting the arrangements"};
+    let mut counts: HashMap<usize, usize> = HashMap::new();
+    for a in all_adapters.iter().rev() {
+        let children = &graph[a];
+        let count = if children.contains(&builtin) {
+            1
+        } else {
+            arith::sum(children.iter().map(|c| *counts.get(c).unwrap_or(&0)), step)?
+        };
+        counts.insert(*a, count);
+    }
+
+    Ok(counts[&0])
+}
+
+// generate writes adapters whose joltages differ by 1 or 3, with at most four
+// differences of 1 in a row as in the real inputs.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut adapters = Vec::new();
+    let (mut jolts, mut ones) = (0, 0);
+    for _ in 0..size {
+        let diff = if ones < 4 && rng.chance(0.6) {1} else {3};
+        ones = if diff == 1 {ones + 1} else {0};
+        jolts += diff;
+        adapters.push(jolts);
+    }
+    rng.shuffle(&mut adapters);
+    adapters.iter().map(|n| format!("{}\n", n)).collect()
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(adapters: &Self::Input) -> Result<Answer, SolveError> {
+        part1(adapters.clone()).map(Answer::from)
+    }
+
+    fn part2(adapters: &Self::Input, _part1: Option<&Answer>) -> Result<Answer, SolveError> {
+        part2(adapters.clone()).map(Answer::from)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String, String> {
+        Ok(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn part1_test() {
+        let input = vec![16, 10, 15, 5, 1, 11, 7, 19, 6, 12, 4];
+        assert_eq!(part1(input), Ok(7 * 5));
+    }
+
+    #[test]
+    fn no_adapters_test() {
+        let err = Err(SolveError::MalformedInput("there are no adapters".to_owned()));
+        assert_eq!(part1(vec![]), err);
+        assert_eq!(part2(vec![]), err);
+        assert!(matches!(part1(vec![1, 8]), Err(SolveError::NoSolution(_))));
+    }
+
+    // The arrangements of a run of adapters one jolt apart grow like the tribonacci
+    // numbers, so a hundred of them is too many to count in a usize.
+    #[cfg(feature = "checked-arith")]
+    #[test]
+    fn overflow_test() {
+        let err = part2((1..=100).collect()).unwrap_err();
+        assert!(matches!(&err, SolveError::Overflow(s) if s.starts_with("day 10, counting the arrangements: ")), "{}", err);
+    }
+}
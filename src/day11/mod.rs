@@ -0,0 +1,259 @@
+use crate::{Answer, ParseError, Solution, SolveError};
+use crate::gen::Rng;
+use crate::grid::{Grid, Pos, DIRECTIONS8};
+use crate::render::{self, Frames, Image};
+use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::DefaultHasher;
+use std::convert::TryFrom;
+use std::hash::{Hash, Hasher};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Status {
+    Floor,
+    Empty,
+    Occupied,
+}
+
+impl TryFrom<char> for Status {
+    type Error = &'static str;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '.' => Ok(Status::Floor),
+            'L' => Ok(Status::Empty),
+            '#' => Ok(Status::Occupied),
+            _ => Err("`.`, `L` or `#`"),
+        }
+    }
+}
+
+impl From<Status> for char {
+    fn from(s: Status) -> char {
+        match s {
+            Status::Floor => '.',
+            Status::Empty => 'L',
+            Status::Occupied => '#',
+        }
+    }
+}
+
+struct Update {
+    pos: Pos,
+    status: Status,
+}
+
+fn occupied_adjacent(grid: &Grid<Status>, pos: Pos) -> usize {
+    grid.neighbours8(pos)
+        .filter(|(_, &s)| s == Status::Occupied)
+        .count()
+}
+
+// occupied_in_sight counts the directions in which the first seat seen is occupied.
+fn occupied_in_sight(grid: &Grid<Status>, pos: Pos) -> usize {
+    DIRECTIONS8.iter()
+        .filter_map(|&dir| grid.ray(pos, dir).map(|(_, &s)| s).find(|&s| s != Status::Floor))
+        .filter(|&s| s == Status::Occupied)
+        .count()
+}
+
+fn count_occupied(grid: &Grid<Status>) -> usize {
+    grid.iter()
+        .filter(|(_, &s)| s == Status::Occupied)
+        .count()
+}
+
+fn update_part1(grid: &Grid<Status>, pos: Pos) -> Option<Update> {
+    let status = grid[pos];
+    match status {
+        Status::Floor => None,
+        Status::Empty | Status::Occupied => {
+            let occ_adjacent = occupied_adjacent(grid, pos);
+            if status == Status::Empty && occ_adjacent == 0 {
+                return Some(Update{pos, status: Status::Occupied});
+            } else if status == Status::Occupied && occ_adjacent >= 4 {
+                return Some(Update{pos, status: Status::Empty});
+            }
+            None
+        },
+    }
+}
+
+fn update_part2(grid: &Grid<Status>, pos: Pos) -> Option<Update> {
+    let status = grid[pos];
+    match status {
+        Status::Floor => None,
+        Status::Empty | Status::Occupied => {
+            let occ_sight = occupied_in_sight(grid, pos);
+            if status == Status::Empty && occ_sight == 0 {
+                return Some(Update{pos, status: Status::Occupied});
+            } else if status == Status::Occupied && occ_sight >= 5 {
+                return Some(Update{pos, status: Status::Empty});
+            }
+            None
+        },
+    }
+}
+
+fn next_step<F>(grid: &Grid<Status>, update: F) -> Vec<Update>
+    where F: Fn(&Grid<Status>, Pos) -> Option<Update>
+{
+    grid.positions()
+        .filter_map(|pos| update(grid, pos))
+        .collect()
+}
+
+// simulate applies `update` until the layout settles, calling `step` with each new
+// layout. Not every layout settles, so one which repeats is an error.
+fn simulate<F, G>(grid: &mut Grid<Status>, update: F, mut step: G) -> Result<(), SolveError>
+    where F: Fn(&Grid<Status>, Pos) -> Option<Update> + Copy, G: FnMut(&Grid<Status>)
+{
+    let mut seen = HashSet::new();
+    for round in 1.. {
+        let updates = next_step(grid, update);
+        if updates.is_empty() {
+            break;
+        }
+        for u in updates.iter() {
+            grid[u.pos] = u.status;
+        }
+        step(grid);
+        if !seen.insert(grid.clone()) {
+            return Err(SolveError::Cycle(format!("the layout after round {} was seen before, so it never settles", round)));
+        }
+    }
+    Ok(())
+}
+
+fn part1(mut grid: Grid<Status>) -> Result<usize, SolveError> {
+    simulate(&mut grid, update_part1, |_| {})?;
+    Ok(count_occupied(&grid))
+}
+
+fn part2(mut grid: Grid<Status>) -> Result<usize, SolveError> {
+    simulate(&mut grid, update_part2, |_| {})?;
+    Ok(count_occupied(&grid))
+}
+
+// oscillating returns the seats which keep changing when `update` is applied forever,
+// or nothing if the layout settles. Not every layout does: some cycle forever.
+fn oscillating<F>(mut grid: Grid<Status>, update: F) -> HashSet<Pos>
+    where F: Fn(&Grid<Status>, Pos) -> Option<Update> + Copy
+{
+    let mut seen = HashMap::new();
+    let mut cycle = None;
+    let mut changing = HashSet::new();
+    for step in 0.. {
+        let updates = next_step(&grid, update);
+        if updates.is_empty() {
+            break;
+        }
+        for u in updates.iter() {
+            grid[u.pos] = u.status;
+        }
+        match cycle {
+            // Once a layout repeats, collect the seats which change over one cycle.
+            Some(end) if step == end => break,
+            Some(_) => changing.extend(updates.iter().map(|u| u.pos)),
+            None => {
+                let mut hasher = DefaultHasher::new();
+                grid.hash(&mut hasher);
+                if let Some(start) = seen.insert(hasher.finish(), step) {
+                    cycle = Some(step + step - start);
+                    changing.extend(updates.iter().map(|u| u.pos));
+                }
+            },
+        }
+    }
+    changing
+}
+
+// generate writes a random square layout. Seats which would keep changing forever
+// under either part's rules are replaced with floor until the layout settles.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut grid = Grid::from_fn(size, size, |_| if rng.chance(0.85) {Status::Empty} else {Status::Floor});
+    loop {
+        let mut seats = oscillating(grid.clone(), update_part1);
+        seats.extend(oscillating(grid.clone(), update_part2));
+        if seats.is_empty() {
+            return format!("{}\n", grid);
+        }
+        for pos in seats {
+            grid[pos] = Status::Floor;
+        }
+    }
+}
+
+// draw draws a layout with each position as a 4x4 square.
+fn draw(grid: &Grid<Status>) -> Image {
+    let image = Image::from_grid(grid, |s| match s {
+        Status::Floor => render::WHITE,
+        Status::Empty => render::GREY,
+        Status::Occupied => render::BLUE,
+    });
+    image.scaled(4)
+}
+
+// render draws every generation of both parts' simulations.
+fn render(grid: &Grid<Status>, frames: &mut Frames) -> Result<(), SolveError> {
+    for (name, update) in [("part1", update_part1 as fn(&_, _) -> _), ("part2", update_part2)].iter() {
+        frames.push(name, draw(grid));
+        simulate(&mut grid.clone(), update, |grid| frames.push(name, draw(grid)))?;
+    }
+    Ok(())
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Grid<Status>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Grid::parse(input)
+    }
+
+    fn part1(grid: &Self::Input) -> Result<Answer, SolveError> {
+        part1(grid.clone()).map(Answer::from)
+    }
+
+    fn part2(grid: &Self::Input, _part1: Option<&Answer>) -> Result<Answer, SolveError> {
+        part2(grid.clone()).map(Answer::from)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String, String> {
+        Ok(generate(rng, size))
+    }
+
+    fn render(grid: &Self::Input, frames: &mut Frames) -> Result<(), String> {
+        render(grid, frames).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn grid() -> impl Strategy<Value = Grid<Status>> {
+        (1..10usize, 1..10usize).prop_flat_map(|(width, height)| {
+            let status = prop_oneof![Just(Status::Floor), Just(Status::Empty), Just(Status::Occupied)];
+            prop::collection::vec(status, width * height).prop_map(move |cells| Grid::new(width, height, cells))
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn display_round_trip_test(grid in grid()) {
+            prop_assert_eq!(Grid::parse(&grid.to_string()), Ok(grid));
+        }
+    }
+
+    #[test]
+    fn cycle_test() {
+        let grid: Grid<Status> = Grid::parse("\
+            LLLLLLLLLLLLLLL\nLLLLLLLLL.LLLLL\nLLLL.LL.LLLL.LL\n.LLL..LLLLLLLLL\n.LLLLLLLLLLL.LL\n\
+            ..LLLLLLLLLLLLL\nLLLLLLL.LLLLLLL\nLLLLLLLLLLLLLLL\nL....L.L.LLLLLL\nLLLL..LLLLLLLLL\n\
+            LLLLL..LLL.LLLL\nLLLL.LLLLLLLLLL\nLLLLL.LLLLLLLLL\nLLL.LLLLLLLLLLL\n..LLLL.LLL.LL.L\n").unwrap();
+        assert!(!oscillating(grid.clone(), update_part1).is_empty());
+        assert!(matches!(part1(grid), Err(SolveError::Cycle(_))));
+    }
+}
@@ -0,0 +1,372 @@
+use crate::{Answer, ParseError, Solution, SolveError};
+use crate::arith::{self, Step};
+use crate::parse::{parse_lines, Cursor};
+use crate::gen::Rng;
+use crate::render::{self, Frames, Image, Rgb};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Degrees {
+    D90,
+    D180,
+    D270,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rotation {
+    Left(Degrees),
+    Right(Degrees),
+}
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Heading {
+    North,
+    South,
+    East,
+    West,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    Move {heading: Heading, amount: i32},
+    Rotate(Rotation),
+    Forward(i32),
+}
+
+#[derive(Debug)]
+struct Boat {
+    x: i32,
+    y: i32,
+    // heading is one of 0 (North), 1 (East), 2 (South), 3 (West)
+    heading: u32
+}
+
+impl Degrees {
+    fn from_int(i: i32) -> Option<Self> {
+        match i {
+            90 => Some(Degrees::D90),
+            180 => Some(Degrees::D180),
+            270 => Some(Degrees::D270),
+            _ => None,
+        }
+    }
+
+    fn to_int(&self) -> i32 {
+        match self {
+            Degrees::D90 => 90,
+            Degrees::D180 => 180,
+            Degrees::D270 => 270,
+        }
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Action::Move {heading: Heading::North, amount} => write!(f, "N{}", amount),
+            Action::Move {heading: Heading::East, amount} => write!(f, "E{}", amount),
+            Action::Move {heading: Heading::South, amount} => write!(f, "S{}", amount),
+            Action::Move {heading: Heading::West, amount} => write!(f, "W{}", amount),
+            Action::Rotate(Rotation::Left(d)) => write!(f, "L{}", d.to_int()),
+            Action::Rotate(Rotation::Right(d)) => write!(f, "R{}", d.to_int()),
+            Action::Forward(amount) => write!(f, "F{}", amount),
+        }
+    }
+}
+
+fn parse_action(c: &mut Cursor) -> Result<Action, ParseError> {
+    let letter = c.next_char(|c| "NESWLRF".contains(c), "one of `N`, `E`, `S`, `W`, `L`, `R` or `F`")?;
+    let degrees_err = c.error("one of `90`, `180` or `270`");
+    let amount = c.number::<i32>()?;
+    c.end()?;
+    let action = match letter {
+        'N' => Action::Move {heading: Heading::North, amount},
+        'E' => Action::Move {heading: Heading::East, amount},
+        'S' => Action::Move {heading: Heading::South, amount},
+        'W' => Action::Move {heading: Heading::West, amount},
+        'L' => Action::Rotate(Rotation::Left(Degrees::from_int(amount).ok_or(degrees_err)?)),
+        'R' => Action::Rotate(Rotation::Right(Degrees::from_int(amount).ok_or(degrees_err)?)),
+        _ => Action::Forward(amount),
+    };
+    Ok(action)
+}
+
+impl FromStr for Action {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_action(&mut Cursor::new(s))
+    }
+}
+#[derive(Debug)]
+struct Waypoint {
+    x: i32,
+    y: i32,
+}
+
+const MOVE_SHIP: Step = Step{day: 12, what: "moving the ship"};
+const MOVE_WAYPOINT: Step = Step{day: 12, what: "moving the waypoint"};
+
+// shift moves a position `amount` in a direction.
+fn shift(x: &mut i32, y: &mut i32, h: Heading, amount: i32, step: Step) -> Result<(), SolveError> {
+    match h {
+        Heading::North => *y = arith::add(*y, amount, step)?,
+        Heading::East => *x = arith::add(*x, amount, step)?,
+        Heading::South => *y = arith::sub(*y, amount, step)?,
+        Heading::West => *x = arith::sub(*x, amount, step)?,
+    }
+    Ok(())
+}
+
+// distance is the Manhattan distance of a position from the origin.
+fn distance(x: i32, y: i32) -> Result<i32, SolveError> {
+    let step = Step{day: 12, what: "measuring the distance"};
+    arith::add(arith::abs(x, step)?, arith::abs(y, step)?, step)
+}
+
+impl Waypoint {
+    fn new() -> Self {
+        Waypoint{x: 10, y: 1}
+    }
+
+    fn move_direction(&mut self, h: Heading, amount: i32) -> Result<(), SolveError> {
+        shift(&mut self.x, &mut self.y, h, amount, MOVE_WAYPOINT)
+    }
+
+    fn rotate(&mut self, r: Rotation) -> Result<(), SolveError> {
+        let neg = |n| arith::sub(0, n, MOVE_WAYPOINT);
+        let (new_x, new_y) = match r {
+            Rotation::Right(Degrees::D90) | Rotation::Left(Degrees::D270) => (self.y, neg(self.x)?),
+            Rotation::Right(Degrees::D180) | Rotation::Left(Degrees::D180)  => (neg(self.x)?, neg(self.y)?),
+            Rotation::Right(Degrees::D270) | Rotation::Left(Degrees::D90) => (neg(self.y)?, self.x),
+        };
+        self.x = new_x;
+        self.y = new_y;
+        Ok(())
+    }
+}
+
+impl Boat {
+    fn new() -> Self {
+        Boat {x: 0, y: 0, heading: 1}
+    }
+
+    fn degree_index(d: Degrees) -> i32 {
+        match d {
+            Degrees::D90 => 1,
+            Degrees::D180 => 2,
+            Degrees::D270 => 3,
+        }
+    }
+
+    fn rotate_index(r: Rotation) -> i32 {
+        match r {
+            Rotation::Right(d) => Self::degree_index(d),
+            Rotation::Left(d) => -Self::degree_index(d)
+        }
+    }
+
+    fn change_heading(&mut self, rotate: Rotation) {
+        let r = Self::rotate_index(rotate);
+        let h = (self.heading as i32 + r) % 4;
+        self.heading = if h < 0 {(4 + h) as u32} else {h as u32};
+    }
+
+    fn move_direction(&mut self, h: Heading, amount: i32) -> Result<(), SolveError> {
+        shift(&mut self.x, &mut self.y, h, amount, MOVE_SHIP)
+    }
+
+    // move_forward moves in the boat's heading, which change_heading keeps below 4.
+    fn move_forward(&mut self, amount: i32) -> Result<(), SolveError> {
+        let heading = match self.heading {
+            0 => Heading::North,
+            1 => Heading::East,
+            2 => Heading::South,
+            _ => Heading::West,
+        };
+        self.move_direction(heading, amount)
+    }
+
+    fn forward_to_waypoint(&mut self, waypoint: &Waypoint, amount: i32) -> Result<(), SolveError> {
+        self.x = arith::add(self.x, arith::mul(amount, waypoint.x, MOVE_SHIP)?, MOVE_SHIP)?;
+        self.y = arith::add(self.y, arith::mul(amount, waypoint.y, MOVE_SHIP)?, MOVE_SHIP)?;
+        Ok(())
+    }
+}
+
+fn parse_input(s: &str) -> Result<Vec<Action>, ParseError> {
+    parse_lines(s, parse_action)
+}
+
+// part1 sails the boat, calling `step` with its position after each action.
+fn part1<F: FnMut(&Boat)>(actions: Vec<Action>, mut step: F) -> Result<i32, SolveError> {
+    let mut boat = Boat::new();
+    for action in actions {
+        match action {
+            Action::Move {heading, amount} => boat.move_direction(heading, amount)?,
+            Action::Rotate(r) => boat.change_heading(r),
+            Action::Forward(amount) => boat.move_forward(amount)?,
+        }
+        step(&boat);
+    }
+    distance(boat.x, boat.y)
+}
+
+// part2 sails the boat using the waypoint, calling `step` with the positions of both
+// after each action. An error from `step` stops the boat.
+fn part2<F>(actions: Vec<Action>, mut step: F) -> Result<i32, SolveError>
+    where F: FnMut(&Boat, &Waypoint) -> Result<(), SolveError>
+{
+    let mut boat = Boat::new();
+    let mut waypoint = Waypoint::new();
+    for action in actions {
+        match action {
+            Action::Move {heading, amount} => waypoint.move_direction(heading, amount)?,
+            Action::Rotate(r) => waypoint.rotate(r)?,
+            Action::Forward(amount) => boat.forward_to_waypoint(&waypoint, amount)?,
+        }
+        step(&boat, &waypoint)?;
+    }
+    distance(boat.x, boat.y)
+}
+
+// TRAJECTORY_SIZE is the width or height, whichever is larger, of a drawn trajectory.
+const TRAJECTORY_SIZE: i64 = 800;
+
+// draw_trajectories draws paths through the positions in each trajectory, with north
+// at the top. The drawing is scaled to fit all of them.
+fn draw_trajectories(trajectories: &[(&[(i32, i32)], Rgb)]) -> Image {
+    let points = || trajectories.iter().flat_map(|(t, _)| t.iter()).map(|&(x, y)| (x as i64, y as i64));
+    let (min_x, max_x) = points().fold((0, 0), |(lo, hi), (x, _)| (lo.min(x), hi.max(x)));
+    let (min_y, max_y) = points().fold((0, 0), |(lo, hi), (_, y)| (lo.min(y), hi.max(y)));
+    let span = (max_x - min_x).max(max_y - min_y).max(1);
+    let to_image = |(x, y): (i32, i32)| {
+        ((x as i64 - min_x) * (TRAJECTORY_SIZE - 1) / span, (max_y - y as i64) * (TRAJECTORY_SIZE - 1) / span)
+    };
+    let width = (max_x - min_x) * (TRAJECTORY_SIZE - 1) / span + 1;
+    let height = (max_y - min_y) * (TRAJECTORY_SIZE - 1) / span + 1;
+    let mut image = Image::new(width as usize, height as usize, render::WHITE);
+    for (trajectory, colour) in trajectories {
+        for pair in trajectory.windows(2) {
+            image.line(to_image(pair[0]), to_image(pair[1]), *colour);
+        }
+    }
+    let (x, y) = to_image((0, 0));
+    image.fill_rect((x - 2, y - 2), (5, 5), render::RED);
+    image
+}
+
+// render draws the boat's trajectory in part 1, and in part 2 the waypoint's as well.
+fn render(actions: &[Action], frames: &mut Frames) -> Result<(), SolveError> {
+    let mut boat_path = vec![(0, 0)];
+    part1(actions.to_vec(), |boat| boat_path.push((boat.x, boat.y)))?;
+    frames.push("part1", draw_trajectories(&[(&boat_path, render::BLUE)]));
+
+    let waypoint = Waypoint::new();
+    let mut boat_path = vec![(0, 0)];
+    let mut waypoint_path = vec![(waypoint.x, waypoint.y)];
+    let step = Step{day: 12, what: "drawing the waypoint"};
+    part2(actions.to_vec(), |boat, waypoint| {
+        boat_path.push((boat.x, boat.y));
+        waypoint_path.push((arith::add(boat.x, waypoint.x, step)?, arith::add(boat.y, waypoint.y, step)?));
+        Ok(())
+    })?;
+    frames.push("part2", draw_trajectories(&[(&waypoint_path, render::ORANGE), (&boat_path, render::BLUE)]));
+    Ok(())
+}
+
+fn generate(rng: &mut Rng, size: usize) -> String {
+    const HEADINGS: [Heading; 4] = [Heading::North, Heading::East, Heading::South, Heading::West];
+    const DEGREES: [Degrees; 3] = [Degrees::D90, Degrees::D180, Degrees::D270];
+    (0..size).map(|_| {
+        let action = match rng.below(3) {
+            0 => Action::Move {heading: rng.choose(&HEADINGS).clone(), amount: rng.range(1, 100) as i32},
+            1 if rng.chance(0.5) => Action::Rotate(Rotation::Left(rng.choose(&DEGREES).clone())),
+            1 => Action::Rotate(Rotation::Right(rng.choose(&DEGREES).clone())),
+            _ => Action::Forward(rng.range(1, 100) as i32),
+        };
+        format!("{}\n", action)
+    }).collect()
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Vec<Action>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(actions: &Self::Input) -> Result<Answer, SolveError> {
+        part1(actions.clone(), |_| {}).map(Answer::from)
+    }
+
+    fn part2(actions: &Self::Input, _part1: Option<&Answer>) -> Result<Answer, SolveError> {
+        part2(actions.clone(), |_, _| Ok(())).map(Answer::from)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String, String> {
+        Ok(generate(rng, size))
+    }
+
+    fn render(actions: &Self::Input, frames: &mut Frames) -> Result<(), String> {
+        render(actions, frames).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const ACTIONS: [Action; 5] = [
+            Action::Forward(10),
+            Action::Move {heading: Heading::North, amount: 3},
+            Action::Forward(7),
+            Action::Rotate(Rotation::Right(Degrees::D90)),
+            Action::Forward(11),
+    ];
+
+    #[test]
+    fn part1_test() {
+        assert_eq!(part1(ACTIONS.to_vec(), |_| {}), Ok(25));
+    }
+
+    #[test]
+    fn part2_test() {
+        assert_eq!(part2(ACTIONS.to_vec(), |_, _| Ok(())), Ok(286));
+    }
+
+    #[cfg(feature = "checked-arith")]
+    #[test]
+    fn overflow_test() {
+        let actions = parse_input("F2000000000\nF2000000000\n").unwrap();
+        let err = SolveError::Overflow("day 12, moving the ship: 2000000000 + 2000000000 does not fit in i32".to_owned());
+        assert_eq!(part1(actions.clone(), |_| {}), Err(err));
+        assert!(matches!(part2(actions, |_, _| Ok(())), Err(SolveError::Overflow(_))));
+
+        // The ship stays in range, but the waypoint it is drawn next to does not.
+        let actions = parse_input("N2147483000\nF1\n").unwrap();
+        let err = SolveError::Overflow("day 12, drawing the waypoint: 2147483001 + 2147483001 does not fit in i32".to_owned());
+        assert_eq!(render(&actions, &mut Frames::new()), Err(err));
+    }
+
+    fn action() -> impl Strategy<Value = Action> {
+        let heading = prop_oneof![Just(Heading::North), Just(Heading::East), Just(Heading::South), Just(Heading::West)];
+        let degrees = prop_oneof![Just(Degrees::D90), Just(Degrees::D180), Just(Degrees::D270)];
+        prop_oneof![
+            (heading, any::<i32>()).prop_map(|(heading, amount)| Action::Move {heading, amount}),
+            (any::<bool>(), degrees).prop_map(|(left, d)| Action::Rotate(if left {Rotation::Left(d)} else {Rotation::Right(d)})),
+            any::<i32>().prop_map(Action::Forward),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn display_round_trip_test(action in action()) {
+            prop_assert_eq!(action.to_string().parse::<Action>(), Ok(action));
+        }
+    }
+}
+
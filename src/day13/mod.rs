@@ -0,0 +1,104 @@
+use crate::{Answer, ParseError, Solution, SolveError};
+use crate::parse::{lines, Cursor};
+use crate::gen::Rng;
+
+fn ceil_div(x: usize, y: usize) -> usize {
+    x / y + (if x.is_multiple_of(y) {0} else {1})
+}
+
+/// Notes are the puzzle input: the earliest time a bus can be taken and the schedule,
+/// in which `None` is a bus which is out of service.
+#[derive(Debug)]
+pub struct Notes {
+    earliest: usize,
+    bus_ids: Vec<Option<usize>>,
+}
+
+fn part1(notes: &Notes) -> Result<usize, SolveError> {
+    let earliest = notes.earliest;
+    let mut min_waiting_time = usize::MAX;
+    let mut bus_id = 0;
+    for id in notes.bus_ids.iter().filter_map(|&id| id) {
+        if id == 0 {
+            return Err(SolveError::MalformedInput("bus 0 never leaves".to_owned()));
+        }
+        let wt = ceil_div(earliest, id) * id - earliest;
+        if wt < min_waiting_time {
+            min_waiting_time = wt;
+            bus_id = id;
+        }
+    }
+    if bus_id == 0 {
+        return Err(SolveError::NoSolution("no buses are in service".to_owned()));
+    }
+    Ok(min_waiting_time * bus_id)
+}
+
+fn parse_bus_ids(c: &mut Cursor) -> Result<Vec<Option<usize>>, ParseError> {
+    let mut bus_ids = Vec::new();
+    loop {
+        if c.peek() == Some('x') {
+            c.literal("x")?;
+            bus_ids.push(None);
+        } else {
+            bus_ids.push(Some(c.number()?));
+        }
+        if c.is_empty() {
+            return Ok(bus_ids);
+        }
+        c.literal(",")?;
+    }
+}
+
+// parse_notes parses the earliest time on the first line and the bus IDs on the second.
+fn parse_notes(s: &str) -> Result<Notes, ParseError> {
+    let mut lines = lines(s);
+    let (n, line) = lines.next().ok_or_else(|| ParseError::end_of_input("the earliest departure time"))?;
+    let mut c = Cursor::new(line);
+    let earliest = c.number().map_err(|e| e.with_line(n))?;
+    c.end().map_err(|e| e.with_line(n))?;
+    let (n, line) = lines.next().ok_or_else(|| ParseError::end_of_input("the bus IDs"))?;
+    let bus_ids = parse_bus_ids(&mut Cursor::new(line)).map_err(|e| e.with_line(n))?;
+    if let Some((n, line)) = lines.next() {
+        return Err(ParseError::at(line, 1, "the end of the input").with_line(n));
+    }
+    Ok(Notes{earliest, bus_ids})
+}
+
+// generate writes an earliest departure time and a schedule of `size` entries in which
+// the buses have distinct prime IDs.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut primes: Vec<i64> = (13..1000).filter(|n| (2..*n).take_while(|d| d * d <= *n).all(|d| n % d != 0)).collect();
+    rng.shuffle(&mut primes);
+    let ids: Vec<_> = (0..size.max(1))
+        .map(|i| match primes.last() {
+            Some(_) if i == 0 || rng.chance(0.15) => primes.pop().unwrap().to_string(),
+            _ => "x".to_owned(),
+        })
+        .collect();
+    format!("{}\n{}\n", rng.range(1_000_000, 1_010_000), ids.join(","))
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Notes;
+
+    const PARTS: &'static [u32] = &[1];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_notes(input)
+    }
+
+    fn part1(notes: &Self::Input) -> Result<Answer, SolveError> {
+        part1(notes).map(Answer::from)
+    }
+
+    fn part2(_notes: &Self::Input, _part1: Option<&Answer>) -> Result<Answer, SolveError> {
+        Err(SolveError::NoSolution("day 13 part 2 has not been solved".to_owned()))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String, String> {
+        Ok(generate(rng, size))
+    }
+}
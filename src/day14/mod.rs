@@ -0,0 +1,101 @@
+mod part1;
+mod part2;
+
+use crate::{Answer, ParseError, Solution, SolveError};
+use crate::parse::{parse_lines, Cursor};
+use crate::gen::Rng;
+
+// Line is a single line of the initialization program. The mask is kept as text since
+// the two parts interpret it differently.
+enum Line {
+    Mask(String),
+    Mem {pos: usize, value: usize},
+}
+
+fn parse_line(c: &mut Cursor) -> Result<Line, ParseError> {
+    let err = c.error("`mask` or `mem`");
+    match c.word(|c| c.is_ascii_lowercase(), "`mask` or `mem`")? {
+        "mask" => {
+            c.literal(" = ")?;
+            let mask = c.rest();
+            for _ in 0..36 {
+                c.next_char(|c| c == 'X' || c == '0' || c == '1', "`X`, `0` or `1`")?;
+            }
+            c.end()?;
+            Ok(Line::Mask(mask.to_owned()))
+        },
+        "mem" => {
+            c.literal("[")?;
+            let pos = c.number()?;
+            c.literal("] = ")?;
+            let value = c.number()?;
+            c.end()?;
+            Ok(Line::Mem {pos, value})
+        },
+        _ => Err(err),
+    }
+}
+
+// MASK_BITS is the number of bits in a mask.
+const MASK_BITS: usize = 36;
+
+// mask_bits checks that a mask is 36 of `X`, `0` and `1` and returns each of them
+// with the bit it applies to, starting from the least significant.
+fn mask_bits(s: &str) -> Result<Vec<(usize, char)>, ParseError> {
+    let n = s.chars().count();
+    if n != MASK_BITS {
+        return Err(ParseError::at(s, n.min(MASK_BITS) + 1, "a mask of 36 bits").found(format!("{} bits", n)));
+    }
+    s.chars()
+        .enumerate()
+        .map(|(i, c)| match c {
+            'X' | '0' | '1' => Ok((MASK_BITS - 1 - i, c)),
+            _ => Err(ParseError::at(s, i + 1, "`X`, `0` or `1`")),
+        })
+        .collect()
+}
+
+fn parse_program(s: &str) -> Result<Vec<Line>, ParseError> {
+    parse_lines(s, parse_line)
+}
+
+// generate writes `size` masks each followed by a few writes. Masks have at most nine
+// floating bits so that part 2 writes at most 512 addresses per write.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut program = String::new();
+    for _ in 0..size {
+        let mut mask: Vec<char> = (0..36).map(|_| if rng.chance(0.5) {'1'} else {'0'}).collect();
+        for _ in 0..rng.range(0, 9) {
+            mask[rng.below(36)] = 'X';
+        }
+        program.push_str(&format!("mask = {}\n", mask.into_iter().collect::<String>()));
+        for _ in 0..rng.range(1, 6) {
+            program.push_str(&format!("mem[{}] = {}\n", rng.range(0, 65535), rng.range(0, 1 << 30)));
+        }
+    }
+    program
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    // The two parts interpret the mask differently so the program is parsed once for
+    // each of them.
+    type Input = (Vec<part1::Instruction>, Vec<part2::Instruction>);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok((part1::parse_input(input)?, part2::parse_input(input)?))
+    }
+
+    fn part1((instructions, _): &Self::Input) -> Result<Answer, SolveError> {
+        Ok(part1::part1(instructions).into())
+    }
+
+    fn part2((_, instructions): &Self::Input, _part1: Option<&Answer>) -> Result<Answer, SolveError> {
+        Ok(part2::part2(instructions).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String, String> {
+        Ok(generate(rng, size))
+    }
+}
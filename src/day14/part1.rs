@@ -1,91 +1,105 @@
+use super::{mask_bits, parse_program, Line};
+use crate::ParseError;
 use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
 
 struct Memset {
     pos: usize,
     value: usize,
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 struct Mask {
     on: usize,
     off: usize,
 }
 
-struct Instruction {
+pub struct Instruction {
     mask: Mask,
     memset: Memset,
 }
 
-impl Mask {
-    fn from_str(s: &str) -> Mask {
-        let (on, off) = s.chars()
-            .rev()
-            .enumerate()
+impl FromStr for Mask {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (on, off) = mask_bits(s)?
+            .into_iter()
             .fold((0, 0), |(on, off), (i, c)| {
                 match c {
-                    'X' => (on, off),
                     '1' => (on | (1 << i), off),
                     '0' => (on, off | (1 << i)),
-                    _ => unreachable!(),
+                    _ => (on, off),
                 }
             });
-        Mask{on, off}
+        Ok(Mask{on, off})
     }
+}
 
+impl Mask {
     fn apply(&self, i: usize) -> usize {
         (i | self.on) & !self.off
     }
 }
 
+impl fmt::Display for Mask {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for i in (0..36).rev() {
+            let c = if self.on & (1 << i) != 0 {'1'} else if self.off & (1 << i) != 0 {'0'} else {'X'};
+            write!(f, "{}", c)?;
+        }
+        Ok(())
+    }
+}
+
 
-fn parse_input(s: &str) -> Option<Vec<Instruction>> {
+pub fn parse_input(s: &str) -> Result<Vec<Instruction>, ParseError> {
     let mut instructions = Vec::new();
     let mut cur_mask = Mask {on: 0, off: 0};
-    for line in s.split('\n').filter(|l| !l.is_empty()) {
-        let mut sp = line.split(" = ");
-        let ident = sp.next()?;
-        if ident == "mask" {
-            cur_mask = Mask::from_str(sp.next()?);
-            continue
+    for line in parse_program(s)? {
+        match line {
+            Line::Mask(mask) => cur_mask = mask.parse()?,
+            Line::Mem {pos, value} => instructions.push(Instruction{
+                mask: cur_mask.clone(), 
+                memset: Memset{pos, value},
+            }),
         }
-        assert!(ident.starts_with("mem"));
-        let pos = ident[4..(ident.len()-1)].parse::<usize>().ok()?;
-        let value = sp.next()?.parse::<usize>().ok()?;
-        instructions.push(Instruction{
-            mask: cur_mask.clone(), 
-            memset: Memset{pos, value},
-        });
     }
-    Some(instructions)
+    Ok(instructions)
 }
 
-fn run(instructions: Vec<Instruction>) -> usize{
+fn run(instructions: &[Instruction]) -> usize{
     let memory: HashMap<usize, usize> = instructions.iter()
         .map(|i| (i.memset.pos, i.mask.apply(i.memset.value)))
         .collect();
     memory.values().sum()
 }
 
-pub fn part1(input: &str) -> usize {
-    let instructions = parse_input(input).expect("parsing input");
+pub fn part1(instructions: &[Instruction]) -> usize {
     run(instructions)
 }
 
 
-#[allow(unused_imports)]
+#[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn mask_from_str_test() {
-        let mask = Mask::from_str("XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X");
-        assert_eq!(mask.off, !0b10);
+        let mask = Mask::from_str("XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X").unwrap();
+        assert_eq!(mask.off, 0b10);
         assert_eq!(mask.on, 0b1000000);
+
+        let err = Mask::from_str("XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX2X").unwrap_err();
+        assert_eq!((err.column(), err.expected()), (35, "`X`, `0` or `1`"));
+        assert!(Mask::from_str("X1").is_err());
     }
 
     #[test]
     fn mask_apply_test() {
-        let mask = Mask::from_str("XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X");
+        let mask = Mask::from_str("XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X").unwrap();
         assert_eq!(mask.apply(11), 73);
         assert_eq!(mask.apply(101), 101);
         assert_eq!(mask.apply(0), 64);
@@ -93,12 +107,20 @@ mod tests {
 
     #[test]
     fn part1_test() {
-        let mask = Mask::from_str("XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X");
+        let mask = Mask::from_str("XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X").unwrap();
         let instructions = vec![
             Instruction{mask: mask.clone(), memset: Memset{pos: 8, value: 11}},
             Instruction{mask: mask.clone(), memset: Memset{pos: 7, value: 101}},
             Instruction{mask: mask.clone(), memset: Memset{pos: 8, value: 0}},
         ];
-        assert_eq!(run(instructions), 165);
+        assert_eq!(run(&instructions), 165);
+    }
+
+    proptest! {
+        #[test]
+        fn mask_display_round_trip_test(on in 0usize..1 << 36, off in 0usize..1 << 36) {
+            let mask = Mask{on: on & !off, off};
+            prop_assert_eq!(Mask::from_str(&mask.to_string()), Ok(mask));
+        }
     }
 }
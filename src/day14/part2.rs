@@ -1,4 +1,8 @@
+use super::{mask_bits, parse_program, Line};
+use crate::ParseError;
 use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
 use itertools::Itertools;
 
 struct Memset {
@@ -6,52 +10,57 @@ struct Memset {
     value: usize,    
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 struct Mask {
     on: usize,
     floating: Vec<usize>,
 }
 
-struct Instruction {
+pub struct Instruction {
     mask: Mask,
     memset: Memset,
 }
 
-impl Mask {
-    fn from_str(s: &str) -> Mask {
+impl FromStr for Mask {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut floating = Vec::new();
         let mut on = 0;
-        for (i, c) in s.chars().rev().enumerate() {
+        for (i, c) in mask_bits(s)?.into_iter().rev() {
             match c {
                 'X' => floating.push(i),
                 '1' => on |= 1 << i, 
-                '0' => {},
-                _ => unreachable!()
+                _ => {},
             }
         }
-        Mask{on, floating}
+        Ok(Mask{on, floating})
+    }
+}
+
+impl fmt::Display for Mask {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for i in (0..36).rev() {
+            let c = if self.floating.contains(&i) {'X'} else if self.on & (1 << i) != 0 {'1'} else {'0'};
+            write!(f, "{}", c)?;
+        }
+        Ok(())
     }
 }
 
-fn parse_input(s: &str) -> Option<Vec<Instruction>> {
+pub fn parse_input(s: &str) -> Result<Vec<Instruction>, ParseError> {
     let mut instructions = Vec::new();
     let mut cur_mask = Mask {on: 0, floating: vec![]};
-    for line in s.split('\n').filter(|l| !l.is_empty()) {
-        let mut sp = line.split(" = ");
-        let ident = sp.next()?;
-        if ident == "mask" {
-            cur_mask = Mask::from_str(sp.next()?);
-            continue
+    for line in parse_program(s)? {
+        match line {
+            Line::Mask(mask) => cur_mask = mask.parse()?,
+            Line::Mem {pos, value} => instructions.push(Instruction{
+                mask: cur_mask.clone(), 
+                memset: Memset{pos, value},
+            }),
         }
-        assert!(ident.starts_with("mem"));
-        let pos = ident[4..(ident.len()-1)].parse::<usize>().ok()?;
-        let value = sp.next()?.parse::<usize>().ok()?;
-        instructions.push(Instruction{
-            mask: cur_mask.clone(), 
-            memset: Memset{pos, value},
-        });
     }
-    Some(instructions)
+    Ok(instructions)
 }
 
 // set_bits returns an int which is bitwise 1 at the provided bit offsets and 0 otherwise.
@@ -59,14 +68,12 @@ fn set_bits(bits: &[usize]) -> usize {
     bits.iter().fold(0, |acc, k| acc | (1 << k))
 }
 
-pub fn part2(s: &str) -> usize {
-    let instructions = parse_input(s).expect("parsing input");
+pub fn part2(instructions: &[Instruction]) -> usize {
     let mut memory = HashMap::new();
     for ins in instructions.iter() {
         // All combinations of the mask's floating bits
         let combinations = (0..ins.mask.floating.len()+1)
-            .map(move |i| ins.mask.floating.iter().cloned().combinations(i))
-            .flatten();
+            .flat_map(move |i| ins.mask.floating.iter().cloned().combinations(i));
 
         // Calculate the base mask. Remember to turn off all floating bits.
         let m = (ins.memset.pos | ins.mask.on) & !set_bits(&ins.mask.floating);
@@ -78,19 +85,28 @@ pub fn part2(s: &str) -> usize {
     memory.values().sum()
 }
 
-#[allow(unused_imports)]
+#[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn set_bits_test() {
-        assert_eq!(set_bits(&vec![5, 2, 0]), 0b100101);
+        assert_eq!(set_bits(&[5, 2, 0]), 0b100101);
     }
 
     #[test]
     fn mask_from_str_test() {
-        let mask = Mask::from_str("000000000000000000000000000000X1001X");
+        let mask = Mask::from_str("000000000000000000000000000000X1001X").unwrap();
         assert_eq!(mask.on, 0b10010);
         assert_eq!(mask.floating, vec![0, 5]);
     }
+
+    proptest! {
+        #[test]
+        fn mask_display_round_trip_test(on in 0usize..1 << 36, floating in 0usize..1 << 36) {
+            let mask = Mask{on: on & !floating, floating: (0..36).filter(|i| floating & (1 << i) != 0).collect()};
+            prop_assert_eq!(Mask::from_str(&mask.to_string()), Ok(mask));
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,89 @@
+use crate::{Answer, ParseError, Solution, SolveError};
+use crate::parse::{parse_lines, Cursor};
+use crate::gen::Rng;
+use log::trace;
+use std::collections::HashMap;
+
+// run plays the game until turn `n` and returns the last number spoken, or None if
+// there are no starting numbers.
+fn run(starting: &[usize], n: usize) -> Option<usize> {
+    let mut last_spoken = HashMap::<usize, (usize, Option<usize>)>::new();
+    for (i, n) in starting.iter().enumerate() {
+        trace!(turn = i + 1, spoken = n; "starting number");
+        last_spoken.insert(*n, (i, None));
+    }
+    let mut last = *starting.last()?;
+    for i in starting.len()..n {
+        match last_spoken.get(&last)? {
+            (_, None) => {
+                last_spoken.entry(0)
+                    .and_modify(|v| {*v = (i, Some(v.0))})
+                    .or_insert((i, None));
+                last = 0;
+            },
+            (n, Some(m)) => {
+                let x = n - m ;
+                last_spoken.entry(x)
+                    .and_modify(|v| {*v = (i, Some(v.0))})
+                    .or_insert((i, None));
+                last = x;
+            }
+        }
+        trace!(turn = i + 1, spoken = last; "spoken");
+    }
+    Some(last)
+}
+
+fn part1(starting: &[usize]) -> Option<usize> {
+    run(starting, 2020)
+}
+
+fn part2(starting: &[usize]) -> Option<usize> {
+    run(starting, 30000000)
+}
+
+fn no_starting_numbers() -> SolveError {
+    SolveError::MalformedInput("there are no starting numbers".to_owned())
+}
+
+// generate writes `size` distinct starting numbers.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut numbers: Vec<_> = (0..30).map(|n| n.to_string()).collect();
+    rng.shuffle(&mut numbers);
+    numbers.truncate(size.clamp(1, 30));
+    format!("{}\n", numbers.join(","))
+}
+
+// parse_starting parses a line of comma separated starting numbers.
+fn parse_starting(c: &mut Cursor) -> Result<Vec<usize>, ParseError> {
+    let mut starting = vec![c.number()?];
+    while !c.is_empty() {
+        c.literal(",")?;
+        starting.push(c.number()?);
+    }
+    Ok(starting)
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        // The starting numbers are comma separated, normally all on one line.
+        let lines = parse_lines(input, parse_starting)?;
+        Ok(lines.into_iter().flatten().collect())
+    }
+
+    fn part1(starting: &Self::Input) -> Result<Answer, SolveError> {
+        part1(starting).map(Answer::from).ok_or_else(no_starting_numbers)
+    }
+
+    fn part2(starting: &Self::Input, _part1: Option<&Answer>) -> Result<Answer, SolveError> {
+        part2(starting).map(Answer::from).ok_or_else(no_starting_numbers)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String, String> {
+        Ok(generate(rng, size))
+    }
+}
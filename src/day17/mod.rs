@@ -0,0 +1,234 @@
+use crate::{Answer, ParseError, Solution, SolveError};
+use crate::gen::Rng;
+use crate::grid;
+use crate::render::{self, Frames, Image};
+use itertools::iproduct;
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct Pos3 {
+    x: i64,
+    y: i64,
+    z: i64,
+}
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct Pos4 {
+    x: i64,
+    y: i64,
+    z: i64,
+    w: i64,
+}
+
+pub trait Pos where
+    Self: Eq + std::hash::Hash + Sized + Clone
+{
+    fn neighbors(&self) -> Box<dyn Iterator<Item=Self> + '_>;
+
+    /// The coordinates as `[x, y, z, w]`, with w 0 in three dimensions.
+    fn coords(&self) -> [i64; 4];
+}
+
+
+impl Pos for Pos3 {
+    fn neighbors(&self) ->  Box<dyn Iterator<Item=Self> + '_> {
+        let it = iproduct!(-1..2, -1..2, -1..2)
+            .filter_map(move |delta| {
+                match delta {
+                    (0, 0, 0) => None,
+                    (dx, dy, dz) => Some(Pos3{x: self.x + dx, y: self.y + dy, z: self.z + dz})
+                }
+            });
+        Box::new(it)
+    }
+
+    fn coords(&self) -> [i64; 4] {
+        [self.x, self.y, self.z, 0]
+    }
+}
+
+impl Pos for Pos4 {
+    fn neighbors(&self) -> Box<dyn Iterator<Item=Self> + '_> {
+        let it = iproduct!(-1..2, -1..2, -1..2, -1..2)
+            .filter_map(move |delta| {
+                match delta {
+                    (0, 0, 0, 0) => None,
+                    (dx, dy, dz, dw) => Some(Pos4{x: self.x + dx, y: self.y + dy, z: self.z + dz, w: self.w + dw})
+                }
+            });
+        Box::new(it)
+    }
+
+    fn coords(&self) -> [i64; 4] {
+        [self.x, self.y, self.z, self.w]
+    }
+}
+
+#[derive(Clone)]
+pub struct Grid<P: Pos> {
+    active_cubes: HashSet<P>
+}
+
+impl<P: Pos> Grid<P> {
+    fn updates(&self) -> HashMap<P, bool> {
+        let mut inactive_cubes = HashMap::new();
+        let mut res = HashMap::new();
+
+        for cube in self.active_cubes.iter() {
+            let mut n_active_neighbors = 0;
+            for pos in cube.neighbors() {
+                if self.active_cubes.contains(&pos) {
+                    n_active_neighbors += 1;
+                } else {
+                    let n = inactive_cubes.entry(pos).or_insert(0);
+                    *n += 1;
+                }
+            }
+            if !(n_active_neighbors == 2 || n_active_neighbors == 3) {
+                res.insert(cube.clone(), false);
+            }
+        }
+
+        for cube in inactive_cubes.into_iter().filter(|(_, v)| v == &3).map(|(k, _)| k) {
+            res.insert(cube.clone(), true);
+        }
+
+        res
+    }
+
+    fn update_grid(&mut self) {
+        let updates = self.updates();
+        for (cube, &on) in updates.iter() {
+            if on {
+                self.active_cubes.insert(cube.clone());
+            } else {
+                self.active_cubes.remove(cube);
+            }
+        }
+    }
+
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cube {
+    Inactive,
+    Active,
+}
+
+impl TryFrom<char> for Cube {
+    type Error = &'static str;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '.' => Ok(Cube::Inactive),
+            '#' => Ok(Cube::Active),
+            _ => Err("`.` or `#`"),
+        }
+    }
+}
+
+// parse_slice returns the x and y coordinates of the active cubes in the initial slice.
+fn parse_slice(s: &str) -> Result<Vec<(i64, i64)>, ParseError> {
+    let slice = grid::Grid::<Cube>::parse(s)?;
+    Ok(slice.iter().filter(|(_, &c)| c == Cube::Active).map(|(pos, _)| pos).collect())
+}
+
+fn parse_input3(s: &str) -> Result<Grid<Pos3>, ParseError> {
+    let active_cubes = parse_slice(s)?.into_iter()
+        .map(|(x, y)| Pos3{x, y, z: 0})
+        .collect();
+    Ok(Grid{active_cubes})
+}
+
+fn parse_input4(s: &str) -> Result<Grid<Pos4>, ParseError> {
+    let active_cubes = parse_slice(s)?.into_iter()
+        .map(|(x, y)| Pos4{x, y, z: 0, w: 0})
+        .collect();
+    Ok(Grid{active_cubes})
+}
+
+// CYCLES is the number of cycles the pocket dimension is simulated for.
+const CYCLES: i64 = 6;
+
+fn part1(mut grid: Grid<Pos3>) -> usize {
+    for _ in 0..CYCLES {
+        grid.update_grid();
+    }
+    grid.active_cubes.len()
+}
+
+fn part2(mut grid: Grid<Pos4>) -> usize {
+    for _ in 0..CYCLES {
+        grid.update_grid();
+    }
+    grid.active_cubes.len()
+}
+
+fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| (0..size).map(|_| if rng.chance(0.4) {'#'} else {'.'}).collect::<String>() + "\n")
+        .collect()
+}
+
+// draw_slices draws the z slices of the cubes across the image and the w slices down
+// it, for w from -w_max to w_max. Each slice covers the initial `size` plus as far as
+// the cubes can spread.
+fn draw_slices<P: Pos>(grid: &Grid<P>, (width, height): (i64, i64), w_max: i64) -> Image {
+    let (slice_w, slice_h) = (width + 2 * CYCLES, height + 2 * CYCLES);
+    let (across, down) = (2 * CYCLES + 1, 2 * w_max + 1);
+    let mut image = Image::new(((slice_w + 1) * across) as usize, ((slice_h + 1) * down) as usize, render::WHITE);
+    for i in 0..across {
+        for j in 0..down {
+            image.fill_rect((i * (slice_w + 1), j * (slice_h + 1)), (slice_w, slice_h), render::GREY);
+        }
+    }
+    for cube in grid.active_cubes.iter() {
+        let [x, y, z, w] = cube.coords();
+        let x = (z + CYCLES) * (slice_w + 1) + x + CYCLES;
+        let y = (w + w_max) * (slice_h + 1) + y + CYCLES;
+        image.set(x, y, render::GREEN);
+    }
+    image.scaled(4)
+}
+
+// render draws the slices of the pocket dimension after each cycle of both parts.
+fn render((grid3, grid4): &(Grid<Pos3>, Grid<Pos4>), frames: &mut Frames) {
+    let size = grid3.active_cubes.iter().fold((0, 0), |(w, h), p| (w.max(p.x + 1), h.max(p.y + 1)));
+    let (mut grid3, mut grid4) = (grid3.clone(), grid4.clone());
+    for cycle in 0..=CYCLES {
+        if cycle > 0 {
+            grid3.update_grid();
+            grid4.update_grid();
+        }
+        frames.push("part1", draw_slices(&grid3, size, 0));
+        frames.push("part2", draw_slices(&grid4, size, CYCLES));
+    }
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    // Part 1 simulates the cubes in three dimensions and part 2 in four.
+    type Input = (Grid<Pos3>, Grid<Pos4>);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok((parse_input3(input)?, parse_input4(input)?))
+    }
+
+    fn part1((grid, _): &Self::Input) -> Result<Answer, SolveError> {
+        Ok(part1(grid.clone()).into())
+    }
+
+    fn part2((_, grid): &Self::Input, _part1: Option<&Answer>) -> Result<Answer, SolveError> {
+        Ok(part2(grid.clone()).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String, String> {
+        Ok(generate(rng, size))
+    }
+
+    fn render(input: &Self::Input, frames: &mut Frames) -> Result<(), String> {
+        render(input, frames);
+        Ok(())
+    }
+}
@@ -0,0 +1,371 @@
+use crate::{Answer, ParseError, Solution, SolveError};
+use crate::arith::{self, Step};
+use crate::parse::{parse_lines, Cursor};
+use crate::gen::Rng;
+use log::{debug, trace};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Token {
+    Open,
+    Close,
+    Add,
+    Multiply,
+    Num(usize),
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Expr {
+    tokens: Vec<Token>
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Token::Open => write!(f, "("),
+            Token::Close => write!(f, ")"),
+            Token::Add => write!(f, "+"),
+            Token::Multiply => write!(f, "*"),
+            Token::Num(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+// Expr is displayed with spaces between tokens except inside parentheses, as in
+// `2 * (3 + 4)`.
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut prev = None;
+        for &token in self.tokens.iter() {
+            if prev.is_some() && prev != Some(Token::Open) && token != Token::Close {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", token)?;
+            prev = Some(token);
+        }
+        Ok(())
+    }
+}
+
+// show_stack writes an evaluation stack bottom first, with its tokens separated by
+// spaces.
+fn show_stack(stack: &[Token]) -> String {
+    let tokens: Vec<_> = stack.iter().map(|t| t.to_string()).collect();
+    format!("[{}]", tokens.join(" "))
+}
+
+impl Expr {
+    // malformed is the error for an expression whose tokens are in an order which
+    // cannot be evaluated, such as `1 +` or `(2 * 3`.
+    fn malformed(&self) -> SolveError {
+        SolveError::MalformedInput(format!("`{}` is not a valid expression", self))
+    }
+
+    fn eval1(&self) -> Result<usize, SolveError> {
+        let step = Step{day: 18, what: "evaluating with equal precedence"};
+        let mut tokens = self.tokens.iter();
+        let pop = |stack: &mut Vec<Token>| stack.pop().ok_or_else(|| self.malformed());
+        let mut stack = vec![*tokens.next().ok_or_else(|| self.malformed())?];
+        loop {
+            trace!(stack:% = show_stack(&stack); "eval1");
+            let head = pop(&mut stack)?;
+            match head {
+                Token::Close => {
+                    let num = pop(&mut stack)?;
+                    let open = pop(&mut stack)?;
+                    match (open, num) {
+                        (Token::Open, Token::Num(_)) => stack.push(num),
+                        _ => return Err(self.malformed()),
+                    }
+                },
+                Token::Num(n) => {
+                    match stack.pop() {
+                        None => {
+                            match tokens.next() {
+                                None => return Ok(n),
+                                Some(token) => {
+                                    stack.push(head);
+                                    stack.push(*token);
+                                }
+                            }
+                        },
+                        Some(Token::Multiply) => {
+                            match pop(&mut stack)? {
+                                Token::Num(m) => stack.push(Token::Num(arith::mul(m, n, step)?)),
+                                _ => return Err(self.malformed()),
+                            }
+                        },
+                        Some(Token::Add) => {
+                            match pop(&mut stack)? {
+                                Token::Num(m) => stack.push(Token::Num(arith::add(m, n, step)?)),
+                                _ => return Err(self.malformed()),
+                            }
+                        },
+                        Some(Token::Open) => {
+                            if let Some(next) = tokens.next() {
+                                stack.push(Token::Open);
+                                stack.push(head);
+                                stack.push(*next);
+                            } else {
+                                stack.push(head);
+                            }
+                        },
+                        _ => return Err(self.malformed()),
+                    }
+                },
+                _ => {
+                    stack.push(head);
+                    stack.push(*tokens.next().ok_or_else(|| self.malformed())?);
+                }
+            }
+        }
+    }
+
+    fn eval2(&self) -> Result<usize, SolveError> {
+        let step = Step{day: 18, what: "evaluating with addition first"};
+        let mut tokens = self.tokens.iter();
+        let pop = |stack: &mut Vec<Token>| stack.pop().ok_or_else(|| self.malformed());
+        let mut stack = vec![*tokens.next().ok_or_else(|| self.malformed())?];
+        loop {
+            trace!(stack:% = show_stack(&stack); "eval2");
+            let head = pop(&mut stack)?;
+            match head {
+                Token::Close => {
+                    // Should be a sequence of multiplies until the Token::Open
+                    let mut sub_res = 1;
+                    loop {
+                        match pop(&mut stack)? {
+                            Token::Num(n) => sub_res = arith::mul(sub_res, n, step)?,
+                            Token::Multiply => {},
+                            Token::Open => break,
+                            _ => return Err(self.malformed()),
+                        }
+                    }
+                    stack.push(Token::Num(sub_res));
+                },
+                Token::Num(n) => {
+                    match stack.pop() {
+                        None => {
+                            match tokens.next() {
+                                None => return Ok(n),
+                                Some(token) => {
+                                    stack.push(head);
+                                    stack.push(*token);
+                                }
+                            }
+                        },
+                        Some(Token::Add) => {
+                            match pop(&mut stack)? {
+                                Token::Num(m) => stack.push(Token::Num(arith::add(n, m, step)?)),
+                                _ => return Err(self.malformed()),
+                            }
+                        },
+                        Some(Token::Multiply) => {
+                            if let Some(token) = tokens.next() {
+                                stack.push(Token::Multiply);
+                                stack.push(head);
+                                stack.push(*token);
+                            } else {
+                                match pop(&mut stack)? {
+                                    Token::Num(m) => stack.push(Token::Num(arith::mul(n, m, step)?)),
+                                    _ => return Err(self.malformed()),
+                                }
+                            }
+                        },
+                        Some(Token::Open) => {
+                            if let Some(next) = tokens.next() {
+                                stack.push(Token::Open);
+                                stack.push(head);
+                                stack.push(*next);
+                            } else {
+                                stack.push(head);
+                            }
+                        },
+                        _ => return Err(self.malformed()),
+                    }
+                },
+                _ => {
+                    stack.push(head);
+                    stack.push(*tokens.next().ok_or_else(|| self.malformed())?);
+                }
+            }
+        }
+    }
+}
+
+fn tokenise(expr: &str) -> Result<Expr, ParseError> {
+    let mut c = Cursor::new(expr);
+    let mut tokens = Vec::new();
+    loop {
+        c.take_while(char::is_whitespace);
+        if c.is_empty() {
+            break;
+        }
+        if c.peek().filter(char::is_ascii_digit).is_some() {
+            let err = c.error("a number");
+            let n = c.take_while(|c| c.is_ascii_digit()).parse::<usize>()
+                .map_err(|_| err.found("a number that is out of range"))?;
+            tokens.push(Token::Num(n));
+            continue;
+        }
+        let token = match c.next_char(|c| "()+*".contains(c), "a number, `(`, `)`, `+` or `*`")? {
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '+' => Token::Add,
+            _ => Token::Multiply,
+        };
+        tokens.push(token);
+    }
+    
+    Ok(Expr{tokens})
+}
+
+fn parse_input(s: &str) -> Result<Vec<Expr>, ParseError> {
+    parse_lines(s, |c| tokenise(c.text()))
+}
+
+const SUM: Step = Step{day: 18, what: "adding up the values"};
+
+fn part1(exprs: &[Expr]) -> Result<usize, SolveError> {
+    exprs.iter().try_fold(0, |total, expr| {
+        let value = expr.eval1()?;
+        debug!(expr:% = expr, value; "evaluated");
+        arith::add(total, value, SUM)
+    })
+}
+
+fn part2(exprs: &[Expr]) -> Result<usize, SolveError> {
+    exprs.iter().try_fold(0, |total, expr| {
+        let value = expr.eval2()?;
+        debug!(expr:% = expr, value; "evaluated");
+        arith::add(total, value, SUM)
+    })
+}
+
+// generate_expr writes an expression of at most `budget` numbers, nesting parentheses
+// up to `depth` deep. Limiting the numbers keeps the results from overflowing.
+fn generate_expr(rng: &mut Rng, depth: usize, budget: &mut usize) -> String {
+    let mut expr = String::new();
+    for i in 0..rng.range(2, 4) {
+        if *budget == 0 {
+            break;
+        }
+        if i > 0 {
+            expr.push_str(if rng.chance(0.5) {" + "} else {" * "});
+        }
+        if depth > 0 && *budget > 2 && rng.chance(0.3) {
+            expr.push_str(&format!("({})", generate_expr(rng, depth - 1, budget)));
+        } else {
+            *budget -= 1;
+            expr.push_str(&rng.range(1, 9).to_string());
+        }
+    }
+    expr
+}
+
+fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size).map(|_| generate_expr(rng, 3, &mut 12) + "\n").collect()
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Vec<Expr>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(exprs: &Self::Input) -> Result<Answer, SolveError> {
+        part1(exprs).map(Answer::from)
+    }
+
+    fn part2(exprs: &Self::Input, _part1: Option<&Answer>) -> Result<Answer, SolveError> {
+        part2(exprs).map(Answer::from)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String, String> {
+        Ok(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn tokenise_test() {
+        let expr = "2 * 3 + (4 * 5)";
+        assert_eq!(
+            tokenise(expr), 
+            Ok(Expr{tokens: vec![
+                Token::Num(2),
+                Token::Multiply,
+                Token::Num(3),
+                Token::Add,
+                Token::Open,
+                Token::Num(4),
+                Token::Multiply,
+                Token::Num(5),
+                Token::Close,
+            ]})
+        )
+    }
+
+    #[test]
+    fn expr_eval_test() {
+        let expr = tokenise("2 * 3 + (4 * 5)").unwrap();
+        assert_eq!(expr.eval1(), Ok(26));
+
+        let expr = tokenise("1 + (2 * 3) + (4 * (5 + 6)").unwrap();
+        assert_eq!(expr.eval1(), Ok(51));
+
+        let expr = tokenise("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2").unwrap();
+        assert_eq!(expr.eval1(), Ok(13632));
+
+    }
+
+    #[test]
+    fn malformed_expr_test() {
+        for expr in ["", "1 +", "* 2", "(1 + 2))", "1 2", "+ )"] {
+            let expr = tokenise(expr).unwrap();
+            assert_eq!(expr.eval1(), Err(expr.malformed()), "{}", expr);
+            assert_eq!(expr.eval2(), Err(expr.malformed()), "{}", expr);
+        }
+    }
+
+    #[cfg(feature = "checked-arith")]
+    #[test]
+    fn overflow_test() {
+        let expr = tokenise("2 * (4294967296 * 4294967296)").unwrap();
+        let err = SolveError::Overflow(
+            "day 18, evaluating with equal precedence: 4294967296 * 4294967296 does not fit in usize".to_owned());
+        assert_eq!(expr.eval1(), Err(err));
+        assert!(matches!(expr.eval2(), Err(SolveError::Overflow(_))));
+    }
+
+    #[test]
+    fn expr_display_test() {
+        let expr = tokenise("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6)").unwrap();
+        assert_eq!(expr.to_string(), "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6)");
+    }
+
+    fn token() -> impl Strategy<Value = Token> {
+        prop_oneof![
+            Just(Token::Open),
+            Just(Token::Close),
+            Just(Token::Add),
+            Just(Token::Multiply),
+            any::<usize>().prop_map(Token::Num),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn expr_display_round_trip_test(tokens in prop::collection::vec(token(), 0..20)) {
+            let expr = Expr{tokens};
+            prop_assert_eq!(tokenise(&expr.to_string()), Ok(expr));
+        }
+    }
+}
\ No newline at end of file
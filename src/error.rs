@@ -0,0 +1,182 @@
+use std::{error, fmt, path::{Path, PathBuf}};
+
+/// ParseError is returned when a day's puzzle input cannot be parsed. It records where
+/// in the input the problem is and is displayed as a compiler-style diagnostic:
+///
+/// ```text
+/// error: expected `-`, found `x3`
+///  --> src/day02/input.txt:3:2
+///   |
+/// 3 | 1x3 a: abcde
+///   |  ^^
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    file: Option<PathBuf>,
+    line: usize,
+    column: usize,
+    width: usize,
+    text: String,
+    expected: String,
+    found: String,
+}
+
+// token_at returns the text found at byte offset `pos` of `text` and its width in
+// characters. A token is a run of alphanumeric characters, or else a single character.
+fn token_at(text: &str, pos: usize) -> (String, usize) {
+    let rest = &text[pos..];
+    let mut chars = rest.chars();
+    match chars.next() {
+        None => ("end of line".to_owned(), 1),
+        Some(c) if c.is_alphanumeric() => {
+            let token: String = rest.chars().take_while(|c| c.is_alphanumeric()).collect();
+            let width = token.chars().count();
+            (format!("`{}`", token), width)
+        },
+        Some(c) => (format!("`{}`", c), 1),
+    }
+}
+
+impl ParseError {
+    /// Creates an error at the 1-based `column` of the line `text`. What was found is
+    /// taken from the text at that column. The line number defaults to 1 and is set by
+    /// `with_line` when the line is part of a larger input.
+    pub fn at(text: &str, column: usize, expected: impl Into<String>) -> Self {
+        let pos = text.char_indices().nth(column - 1).map(|(i, _)| i).unwrap_or(text.len());
+        let (found, width) = token_at(text, pos);
+        ParseError{
+            file: None,
+            line: 1,
+            column,
+            width,
+            text: text.to_owned(),
+            expected: expected.into(),
+            found,
+        }
+    }
+
+    /// Creates an error for input that ended before something that was expected.
+    pub fn end_of_input(expected: impl Into<String>) -> Self {
+        ParseError{
+            file: None,
+            line: 1,
+            column: 1,
+            width: 1,
+            text: String::new(),
+            expected: expected.into(),
+            found: "end of input".to_owned(),
+        }
+    }
+
+    /// Replaces the description of what was found, e.g. when the problem is not a
+    /// single token.
+    pub fn found(mut self, found: impl Into<String>) -> Self {
+        self.found = found.into();
+        self
+    }
+
+    pub fn with_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+
+    pub fn with_file(mut self, file: impl Into<PathBuf>) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+
+    pub fn file(&self) -> Option<&Path> {
+        self.file.as_deref()
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn expected(&self) -> &str {
+        &self.expected
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let file = self.file.as_ref()
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| "<input>".to_owned());
+        let line = self.line.to_string();
+        let pad = " ".repeat(line.len());
+        writeln!(f, "error: expected {}, found {}", self.expected, self.found)?;
+        writeln!(f, "{}--> {}:{}:{}", pad, file, self.line, self.column)?;
+        writeln!(f, "{} |", pad)?;
+        writeln!(f, "{} | {}", line, self.text)?;
+        write!(f, "{} | {}{}", pad, " ".repeat(self.column - 1), "^".repeat(self.width))
+    }
+}
+
+impl error::Error for ParseError {}
+
+/// SolveError is returned when a part cannot be solved for an input which parsed. Each
+/// variant says what went wrong in words.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// The input is well formed but has no answer, e.g. no two entries sum to 2020.
+    NoSolution(String),
+    /// The input parsed but breaks one of the puzzle's rules, e.g. a bag which contains
+    /// a bag that has no rule.
+    MalformedInput(String),
+    /// A number grew too large for the type holding it.
+    Overflow(String),
+    /// A process which should finish repeats forever.
+    Cycle(String),
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SolveError::NoSolution(s) => write!(f, "error: no solution: {}", s),
+            SolveError::MalformedInput(s) => write!(f, "error: malformed input: {}", s),
+            SolveError::Overflow(s) => write!(f, "error: overflow: {}", s),
+            SolveError::Cycle(s) => write!(f, "error: cycle: {}", s),
+        }
+    }
+}
+
+impl error::Error for SolveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_test() {
+        let err = ParseError::at("1x3 a: abcde", 2, "`-`")
+            .with_line(3)
+            .with_file("input.txt");
+        assert_eq!(err.to_string(), "\
+error: expected `-`, found `x3`
+ --> input.txt:3:2
+  |
+3 | 1x3 a: abcde
+  |  ^^");
+    }
+
+    #[test]
+    fn solve_error_display_test() {
+        let err = SolveError::NoSolution("no two entries sum to 2020".to_owned());
+        assert_eq!(err.to_string(), "error: no solution: no two entries sum to 2020");
+    }
+
+    #[test]
+    fn end_of_line_test() {
+        let err = ParseError::at("acc", 4, "a number");
+        assert_eq!(err.to_string().lines().next(), Some("error: expected a number, found end of line"));
+    }
+}
@@ -0,0 +1,134 @@
+use crate::config::Config;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The year whose puzzles these are.
+pub const YEAR: u32 = 2020;
+
+/// Cache keeps fetched inputs on disk so that each is only downloaded once. Inputs are
+/// stored by content, in `objects/SHA256`, and `days/dayNN` holds the hash of a day's
+/// input.
+#[derive(Debug, Clone)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: &Path) -> Self {
+        Cache{dir: dir.to_owned()}
+    }
+
+    fn object(&self, hash: &str) -> PathBuf {
+        self.dir.join("objects").join(hash)
+    }
+
+    fn entry(&self, day: u32) -> PathBuf {
+        self.dir.join("days").join(format!("day{:02}", day))
+    }
+
+    /// get returns a day's cached input, if it has been fetched.
+    pub fn get(&self, day: u32) -> Option<String> {
+        let hash = fs::read_to_string(self.entry(day)).ok()?;
+        fs::read_to_string(self.object(hash.trim())).ok()
+    }
+
+    /// put stores a day's input and returns its hash.
+    pub fn put(&self, day: u32, text: &str) -> Result<String, String> {
+        let hash = format!("{:x}", Sha256::digest(text.as_bytes()));
+        write_atomic(&self.object(&hash), text)?;
+        write_atomic(&self.entry(day), &format!("{}\n", hash))?;
+        Ok(hash)
+    }
+}
+
+// write_atomic writes a file by writing a temporary file next to it and renaming it, so
+// that an interrupted write never leaves a partial file behind.
+fn write_atomic(path: &Path, text: &str) -> Result<(), String> {
+    let err = |e: std::io::Error| format!("cannot write {}: {}", path.display(), e);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(err)?;
+    }
+    let temp = path.with_extension("tmp");
+    fs::write(&temp, text).map_err(err)?;
+    fs::rename(&temp, path).map_err(err)
+}
+
+/// The user agent sent with every request, so that the server knows who is asking.
+pub const USER_AGENT: &str = concat!("aoc2020/", env!("CARGO_PKG_VERSION"));
+
+/// input_url returns the URL of a day's puzzle input.
+pub fn input_url(base_url: &str, day: u32) -> String {
+    format!("{}/{}/day/{}/input", base_url, YEAR, day)
+}
+
+/// Fetched is a day's puzzle input and whether it came from the cache.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fetched {
+    pub text: String,
+    pub cached: bool,
+}
+
+/// fetch returns a day's puzzle input, downloading it with the session cookie only if
+/// it is not already in the cache.
+pub fn fetch(config: &Config, day: u32) -> Result<Fetched, String> {
+    let cache = Cache::new(&config.cache_dir);
+    if let Some(text) = cache.get(day) {
+        return Ok(Fetched{text, cached: true});
+    }
+    let url = input_url(&config.base_url, day);
+    let response = ureq::get(&url)
+        .set("Cookie", &format!("session={}", config.session()?))
+        .set("User-Agent", USER_AGENT)
+        .call();
+    let text = match response {
+        Ok(response) => response.into_string().map_err(|e| format!("cannot read {}: {}", url, e))?,
+        Err(ureq::Error::Status(code, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            let reason = body.lines().next().unwrap_or("").trim();
+            return Err(format!("cannot fetch {}: the server answered {} {}", url, code, reason));
+        },
+        Err(err) => return Err(format!("cannot fetch {}: {}", url, err)),
+    };
+    cache.put(day, &text)?;
+    Ok(Fetched{text, cached: false})
+}
+
+/// install writes a fetched input to `path`, unless it already holds that input. It
+/// will not replace a different input, which may have been edited by hand, but will
+/// fill in the empty one a new day starts with. It returns whether the file was written.
+pub fn install(path: &Path, text: &str) -> Result<bool, String> {
+    match fs::read_to_string(path) {
+        Ok(existing) if existing == text => Ok(false),
+        Ok(existing) if existing.is_empty() => write_atomic(path, text).map(|_| true),
+        Ok(_) => Err(format!("{} already holds a different input, remove it to replace it", path.display())),
+        Err(_) => write_atomic(path, text).map(|_| true),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_test() {
+        let dir = std::env::temp_dir().join(format!("aoc2020-cache-{}", std::process::id()));
+        let cache = Cache::new(&dir);
+        assert_eq!(cache.get(3), None);
+        let hash = cache.put(3, "..#\n").unwrap();
+        assert_eq!(cache.get(3), Some("..#\n".to_owned()));
+        assert_eq!(fs::read_to_string(dir.join("objects").join(&hash)).unwrap(), "..#\n");
+
+        // Days with the same input share an object.
+        assert_eq!(cache.put(4, "..#\n"), Ok(hash));
+        assert_eq!(fs::read_dir(dir.join("objects")).unwrap().count(), 1);
+
+        let path = dir.join("day03").join("input.txt");
+        assert_eq!(install(&path, "..#\n"), Ok(true));
+        assert_eq!(install(&path, "..#\n"), Ok(false));
+        assert!(install(&path, "#..\n").is_err());
+        fs::write(&path, "").unwrap();
+        assert_eq!(install(&path, "#..\n"), Ok(true));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
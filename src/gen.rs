@@ -0,0 +1,96 @@
+/// Rng is a small seeded pseudo-random number generator (SplitMix64) used to generate
+/// puzzle inputs. It is kept here rather than taken from a crate so that a seed always
+/// generates the same input.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// below returns a number in `0..n`.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0);
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// range returns a number in `lo..=hi`.
+    pub fn range(&mut self, lo: i64, hi: i64) -> i64 {
+        assert!(lo <= hi);
+        lo + (self.next_u64() % (hi - lo + 1) as u64) as i64
+    }
+
+    /// chance returns true with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+
+    /// word returns a pronounceable lowercase word of `n` syllables.
+    pub fn word(&mut self, n: usize) -> String {
+        const CONSONANTS: &[u8] = b"bcdfghklmnprstvwz";
+        const VOWELS: &[u8] = b"aeiou";
+        let mut word = String::new();
+        for _ in 0..n {
+            word.push(*self.choose(CONSONANTS) as char);
+            word.push(*self.choose(VOWELS) as char);
+        }
+        word
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DAYS;
+    use std::iter;
+
+    #[test]
+    fn rng_test() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        for _ in 0..100 {
+            let n = a.range(-3, 3);
+            assert!((-3..=3).contains(&n));
+            assert_eq!(n, b.range(-3, 3));
+        }
+    }
+
+    // Every generated input must parse and be solvable. Day 15's part 2 plays 30 million
+    // turns whatever the input, which takes too long in a debug build, so only its part 1
+    // is run. A new day with no parts solved yet needs no generator.
+    #[test]
+    fn generate_test() {
+        for day in DAYS.iter().filter(|d| d.needs_input && !d.parts.is_empty()) {
+            let parts = if day.number == 15 {&[1]} else {day.parts};
+            for (seed, size) in (0..3).map(|seed| (seed, 12)).chain(iter::once((3, 1))) {
+                let input = (day.generate)(&mut Rng::new(seed), size)
+                    .unwrap_or_else(|err| panic!("day {}: {}", day.number, err));
+                let solved = (day.run)(&input, parts)
+                    .unwrap_or_else(|err| panic!("day {} seed {}: {}", day.number, seed, err));
+                for s in solved.iter() {
+                    if let Err(err) = &s.answer {
+                        panic!("day {} seed {} part {}: {}", day.number, seed, s.part, err);
+                    }
+                }
+            }
+        }
+    }
+}
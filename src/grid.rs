@@ -0,0 +1,251 @@
+use crate::error::ParseError;
+use crate::parse::parse_lines;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// Pos is a position in a grid as `(x, y)`, with `y` increasing down the rows. It is
+/// signed so that positions off the grid can be named.
+pub type Pos = (i64, i64);
+
+/// The directions to the four orthogonally adjacent cells.
+pub const DIRECTIONS4: [Pos; 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// The directions to the eight adjacent cells, including diagonals.
+pub const DIRECTIONS8: [Pos; 8] = [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)];
+
+/// Grid is a rectangular map of cells stored row by row, for the puzzles whose input is
+/// a picture. Cells can be addressed three ways: `get` treats the grid as bounded,
+/// `get_wrapped` as a torus and `get_or` as an infinite plane filled with a given cell.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from its cells in row order.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "a {}x{} grid needs {} cells", width, height, width * height);
+        Grid{width, height, cells}
+    }
+
+    /// Creates a grid by calling `f` for each position in row order.
+    pub fn from_fn<F: FnMut(Pos) -> T>(width: usize, height: usize, f: F) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x as i64, y as i64)))
+            .map(f)
+            .collect();
+        Grid{width, height, cells}
+    }
+
+    /// parse reads a grid with one row per line and one character per cell. Every row
+    /// must be the same length. The error from converting a character describes what was
+    /// expected, e.g. "`.` or `#`".
+    pub fn parse(s: &str) -> Result<Self, ParseError>
+        where T: TryFrom<char>, T::Error: Into<String>
+    {
+        let mut width = None;
+        let rows = parse_lines(s, |c| {
+            let mut row = Vec::new();
+            while let Some(ch) = c.peek() {
+                let cell = T::try_from(ch).map_err(|e| c.error(e))?;
+                c.next_char(|_| true, "a cell")?;
+                row.push(cell);
+            }
+            let n = *width.get_or_insert(row.len());
+            if row.len() != n {
+                let err = ParseError::at(c.text(), n.min(row.len()) + 1, format!("a row of {} cells", n));
+                return Err(err.found(format!("{} cells", row.len())));
+            }
+            Ok(row)
+        })?;
+        let width = width.ok_or_else(|| ParseError::end_of_input("a grid"))?;
+        let height = rows.len();
+        Ok(Grid{width, height, cells: rows.into_iter().flatten().collect()})
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    // offset returns the index into `cells` of a position, if it is on the grid.
+    fn offset(&self, (x, y): Pos) -> Option<usize> {
+        if x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height {
+            Some(y as usize * self.width + x as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        self.offset(pos).is_some()
+    }
+
+    /// get returns the cell at a position, or None if it is off the grid.
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.offset(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.offset(pos).map(move |i| &mut self.cells[i])
+    }
+
+    /// get_wrapped returns the cell at a position on a grid which repeats forever in
+    /// every direction.
+    pub fn get_wrapped(&self, (x, y): Pos) -> &T {
+        let x = x.rem_euclid(self.width as i64);
+        let y = y.rem_euclid(self.height as i64);
+        &self[(x, y)]
+    }
+
+    /// get_or returns the cell at a position, treating every cell off the grid as
+    /// `outside`.
+    pub fn get_or<'a>(&'a self, pos: Pos, outside: &'a T) -> &'a T {
+        self.get(pos).unwrap_or(outside)
+    }
+
+    /// positions iterates over every position on the grid in row order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x as i64, y as i64)))
+    }
+
+    /// iter iterates over every cell on the grid with its position, in row order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    // neighbours returns the cells one step from `pos` in each direction which are on
+    // the grid.
+    fn neighbours<'a>(&'a self, (x, y): Pos, directions: &'a [Pos]) -> impl Iterator<Item = (Pos, &'a T)> {
+        directions.iter()
+            .map(move |(dx, dy)| (x + dx, y + dy))
+            .filter_map(move |pos| Some((pos, self.get(pos)?)))
+    }
+
+    /// neighbours4 iterates over the cells orthogonally adjacent to a position.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> {
+        self.neighbours(pos, &DIRECTIONS4)
+    }
+
+    /// neighbours8 iterates over the cells adjacent to a position, including diagonally.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> {
+        self.neighbours(pos, &DIRECTIONS8)
+    }
+
+    /// ray iterates over the cells seen looking from `pos` in direction `dir`, nearest
+    /// first, until the edge of the grid. The cell at `pos` is not included.
+    pub fn ray(&self, (x, y): Pos, (dx, dy): Pos) -> impl Iterator<Item = (Pos, &T)> {
+        assert!((dx, dy) != (0, 0), "a ray needs a direction");
+        (1..)
+            .map(move |i| (x + i * dx, y + i * dy))
+            .map(move |pos| Some((pos, self.get(pos)?)))
+            .take_while(Option::is_some)
+            .flatten()
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        match self.offset(pos) {
+            Some(i) => &self.cells[i],
+            None => panic!("{:?} is off the {}x{} grid", pos, self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        match self.offset(pos) {
+            Some(i) => &mut self.cells[i],
+            None => panic!("{:?} is off the {}x{} grid", pos, self.width, self.height),
+        }
+    }
+}
+
+// Grid is displayed one row per line, in the format read by parse.
+impl<T: Copy + Into<char>> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, row) in self.cells.chunks(self.width.max(1)).enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            let row: String = row.iter().map(|&c| c.into()).collect();
+            f.write_str(&row)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Cell {
+        Open,
+        Wall,
+    }
+
+    impl TryFrom<char> for Cell {
+        type Error = &'static str;
+
+        fn try_from(c: char) -> Result<Self, Self::Error> {
+            match c {
+                '.' => Ok(Cell::Open),
+                '#' => Ok(Cell::Wall),
+                _ => Err("`.` or `#`"),
+            }
+        }
+    }
+
+    impl From<Cell> for char {
+        fn from(c: Cell) -> char {
+            match c {
+                Cell::Open => '.',
+                Cell::Wall => '#',
+            }
+        }
+    }
+
+    #[test]
+    fn parse_test() {
+        let grid = Grid::<Cell>::parse("..#\n#..\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 0)], Cell::Wall);
+        assert_eq!(grid.to_string(), "..#\n#..");
+
+        let err = Grid::<Cell>::parse("..#\n#x.\n").unwrap_err();
+        assert_eq!((err.line(), err.column(), err.expected()), (2, 2, "`.` or `#`"));
+        let err = Grid::<Cell>::parse("..#\n#.\n").unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 3));
+        assert!(Grid::<Cell>::parse("\n").is_err());
+    }
+
+    #[test]
+    fn addressing_test() {
+        let grid = Grid::<Cell>::parse("#.\n..\n").unwrap();
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.get_wrapped((2, -2)), &Cell::Wall);
+        assert_eq!(grid.get_or((-1, 0), &Cell::Wall), &Cell::Wall);
+        assert_eq!(grid.get_or((1, 0), &Cell::Wall), &Cell::Open);
+    }
+
+    #[test]
+    fn neighbours_test() {
+        let grid = Grid::from_fn(3, 3, |(x, y)| x + 3 * y);
+        let cells = |it: &mut dyn Iterator<Item = (Pos, &i64)>| it.map(|(_, &c)| c).collect::<Vec<_>>();
+        assert_eq!(cells(&mut grid.neighbours4((0, 0))), vec![1, 3]);
+        assert_eq!(cells(&mut grid.neighbours8((1, 1))), vec![0, 1, 2, 3, 5, 6, 7, 8]);
+        assert_eq!(cells(&mut grid.ray((0, 0), (1, 1))), vec![4, 8]);
+        assert_eq!(cells(&mut grid.ray((0, 2), (0, 1))), vec![]);
+    }
+}
@@ -0,0 +1,61 @@
+use crate::solution::Day;
+use std::io::{self, Read};
+use std::{fs, path::{Path, PathBuf}};
+
+/// The `--input` value which reads the puzzle input from stdin.
+pub const STDIN: &str = "-";
+
+/// The directory holding the `dayNN/input.txt` puzzle inputs when none is configured.
+pub const DEFAULT_DIR: &str = "src";
+
+/// path_in returns the path of a day's puzzle input in the inputs directory `dir`.
+pub fn path_in(dir: &Path, day: u32) -> PathBuf {
+    dir.join(format!("day{:02}", day)).join("input.txt")
+}
+
+/// Input is a puzzle input along with where it was read from, which is used to label
+/// parse errors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input {
+    pub source: String,
+    pub text: String,
+}
+
+/// read reads the puzzle input for a day. `file` is either a path or `-` for stdin; if
+/// it is not given the input is read from `dayNN/input.txt` under `dir`. Days with a
+/// built-in input have no input unless one is given.
+pub fn read(day: &Day, file: Option<&str>, dir: &Path) -> Result<Option<Input>, String> {
+    let path = match file {
+        Some(STDIN) => {
+            let mut text = String::new();
+            io::stdin().read_to_string(&mut text).map_err(|e| format!("cannot read stdin: {}", e))?;
+            return Ok(Some(Input{source: "<stdin>".to_owned(), text}));
+        },
+        Some(file) => Path::new(file).to_owned(),
+        None => match day.input_in(dir) {
+            Some(path) => path,
+            None => return Ok(None),
+        },
+    };
+    let text = fs::read_to_string(&path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+    Ok(Some(Input{source: path.display().to_string(), text}))
+}
+
+#[cfg(all(test, feature = "day01"))]
+mod tests {
+    use super::*;
+    use crate::DAYS;
+
+    #[test]
+    fn read_test() {
+        let day1 = &DAYS[0];
+        let err = read(day1, None, Path::new("no/such/dir")).unwrap_err();
+        assert!(err.starts_with("cannot read no/such/dir/day01/input.txt: "), "{}", err);
+
+        let input = read(day1, None, Path::new(DEFAULT_DIR)).unwrap().unwrap();
+        assert_eq!(input.source, "src/day01/input.txt");
+
+        let builtin = Day{needs_input: false, ..Day::new::<crate::day01::Day01>(1)};
+        assert_eq!(read(&builtin, None, Path::new("no/such/dir")), Ok(None));
+    }
+}
@@ -0,0 +1,33 @@
+pub mod allocs;
+pub mod arith;
+pub mod bench;
+#[cfg(feature = "cli")]
+pub mod config;
+pub mod error;
+#[cfg(feature = "cli")]
+pub mod fetch;
+pub mod gen;
+pub mod grid;
+pub mod input;
+pub mod parallel;
+pub mod parse;
+pub mod render;
+pub mod scaffold;
+pub mod solution;
+#[cfg(feature = "cli")]
+pub mod submit;
+pub mod trace;
+pub mod verify;
+#[cfg(feature = "cli")]
+pub mod watch;
+
+pub use error::{ParseError, SolveError};
+pub use solution::{Answer, Day, Solution, Solved};
+
+// The days are discovered by build.rs, which generates a `pub mod dayNN` for each
+// `src/dayNN` directory whose `dayNN` feature is enabled, along with:
+//
+// - `DAYS`, the Day for every solution in order.
+// - `for_each_day!(m)`, which calls the macro `m!(number, module, Type)` for each day,
+//   e.g. `m!(7, day07, Day07)`.
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
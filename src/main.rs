@@ -1,3 +1,624 @@
+use aoc2020::{Day, Solved, DAYS};
+use aoc2020::{allocs, bench, fetch, gen, input, parallel, render, scaffold, trace, watch};
+use aoc2020::config::Config;
+use aoc2020::submit::{self, History, Verdict};
+use aoc2020::verify::{self, Status};
+use clap::{App, AppSettings, Arg, SubCommand};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::io::{self, Write};
+use std::{fs, iter, path::Path, process, thread};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+fn find_day(number: &str) -> Option<&'static Day> {
+    let n = number.parse::<u32>().ok()?;
+    DAYS.iter().find(|d| d.number == n)
+}
+
+fn list() {
+    for day in DAYS.iter() {
+        let parts: Vec<_> = day.parts.iter().map(|p| p.to_string()).collect();
+        let note = if day.needs_input {""} else {" (built-in input)"};
+        println!("day {:02}: part {}{}", day.number, parts.join(", "), note);
+    }
+}
+
+// print_json prints a result as a single line JSON object. The answer is a string so
+// that large answers survive consumers which parse numbers as doubles. A part with no
+// answer has a null answer and an error instead.
+fn print_json(day: &Day, solved: &Solved, input: Option<&str>) {
+    let ms = |d: std::time::Duration| d.as_secs_f64() * 1000.0;
+    let sha256 = input.map(|input| format!("{:x}", Sha256::digest(input.as_bytes())));
+    let result = json!({
+        "day": day.number,
+        "part": solved.part,
+        "answer": solved.answer.as_ref().ok().map(|a| a.to_string()),
+        "error": solved.answer.as_ref().err().map(|e| e.to_string()),
+        "parse_ms": ms(solved.parse_time),
+        "solve_ms": ms(solved.solve_time),
+        "input_sha256": sha256,
+    });
+    println!("{}", result);
+}
+
+// read_input reads a day's input, exiting with an error if it cannot be read.
+fn read_input(day: &Day, input_file: Option<&str>, inputs_dir: &Path) -> Option<input::Input> {
+    input::read(day, input_file, inputs_dir).unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    })
+}
+
+// select_parts returns the parts named by the `part` argument, exiting with an error
+// if one is not implemented.
+fn select_parts(day: &Day, part: &str) -> &'static [u32] {
+    let parts = match part {
+        "both" => day.parts,
+        "1" => &[1],
+        _ => &[2],
+    };
+    if let Some(part) = parts.iter().find(|p| !day.parts.contains(p)) {
+        eprintln!("day {} part {} is not implemented", day.number, part);
+        process::exit(1);
+    }
+    parts
+}
+
+fn run(day: &Day, part: &str, input_file: Option<&str>, inputs_dir: &Path, json: bool,
+       render_to: Option<(&Path, render::Format)>) {
+    let input = read_input(day, input_file, inputs_dir);
+    let text = input.as_ref().map(|i| i.text.as_str()).unwrap_or("");
+    let parts = select_parts(day, part);
+    match (day.run)(text, parts) {
+        Ok(solved) => {
+            for s in solved.iter() {
+                match &s.answer {
+                    _ if json => print_json(day, s, input.as_ref().map(|i| i.text.as_str())),
+                    Ok(answer) if part == "both" => println!("part {}: {}", s.part, answer),
+                    Ok(answer) => println!("{}", answer),
+                    Err(err) => eprintln!("part {}: {}", s.part, err),
+                }
+            }
+            if let Some((dir, format)) = render_to {
+                render(day, text, dir, format);
+            }
+            if solved.iter().any(|s| s.answer.is_err()) {
+                process::exit(1);
+            }
+        },
+        Err(err) => {
+            let err = match input {
+                Some(input) => err.with_file(input.source),
+                None => err,
+            };
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    }
+}
+
+// watch solves a day's input file each time it changes until interrupted. Each time
+// the screen is cleared and the answers are printed with their timings, or the parse
+// error is. JSON results are printed one after another without clearing.
+fn watch(day: &Day, part: &str, path: &Path, json: bool) -> ! {
+    let parts = select_parts(day, part);
+    // The watcher is started before the file is first read so that no change is missed.
+    let mut watcher = watch::Watcher::new(path).unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    });
+    loop {
+        if !json {
+            print!("\x1b[2J\x1b[H");
+            println!("day {}: watching {}, press Ctrl-C to stop\n", day.number, path.display());
+        }
+        match fs::read_to_string(path) {
+            Ok(text) => match (day.run)(&text, parts) {
+                Ok(solved) => for s in solved.iter() {
+                    match &s.answer {
+                        _ if json => print_json(day, s, Some(&text)),
+                        Ok(answer) => println!("part {}: {}  (parse {:.2?}, solve {:.2?})",
+                                               s.part, answer, s.parse_time, s.solve_time),
+                        Err(err) => println!("part {}: {}", s.part, err),
+                    }
+                },
+                Err(err) => println!("{}", err.with_file(path)),
+            },
+            Err(err) => println!("error: cannot read {}: {}", path.display(), err),
+        }
+        let _ = io::stdout().flush();
+        if let Err(err) = watcher.wait() {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        }
+    }
+}
+
+// fetch downloads a day's puzzle input, unless it is already in the cache, and writes
+// it to the inputs directory.
+fn fetch(day: u32, inputs_dir: &Path) {
+    let fetched = Config::load().and_then(|config| fetch::fetch(&config, day)).unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    });
+    let path = input::path_in(inputs_dir, day);
+    let from = if fetched.cached {"the cache"} else {"the server"};
+    match fetch::install(&path, &fetched.text) {
+        Ok(true) => eprintln!("day {}: wrote the input from {} to {}", day, from, path.display()),
+        Ok(false) => eprintln!("day {}: {} is up to date", day, path.display()),
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        },
+    }
+}
+
+// submit solves one part of a day and posts the answer to the server, printing its
+// verdict. It exits with an error unless the answer was right.
+fn submit(day: &Day, part: &str, input_file: Option<&str>, inputs_dir: &Path) {
+    let input = read_input(day, input_file, inputs_dir);
+    let text = input.as_ref().map(|i| i.text.as_str()).unwrap_or("");
+    let parts = select_parts(day, part);
+    let answer = match (day.run)(text, parts) {
+        Ok(mut solved) => match solved.pop().map(|s| s.answer) {
+            Some(Ok(answer)) => answer.to_string(),
+            Some(Err(err)) => {
+                eprintln!("part {}: {}", part, err);
+                process::exit(1);
+            },
+            None => unreachable!(),
+        },
+        Err(err) => {
+            let err = match input {
+                Some(input) => err.with_file(input.source),
+                None => err,
+            };
+            eprintln!("{}", err);
+            process::exit(1);
+        },
+    };
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let response = Config::load()
+        .and_then(|config| {
+            let mut history = History::load(&config.history)?;
+            submit::submit(&config, &mut history, day.number, parts[0], &answer, now)
+        })
+        .unwrap_or_else(|err| {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        });
+    let verdict = match response.verdict {
+        Verdict::Right => "right",
+        Verdict::Wrong => "wrong",
+        Verdict::TooHigh => "wrong, too high",
+        Verdict::TooLow => "wrong, too low",
+        Verdict::Wait => "not checked, an answer was submitted too recently",
+        Verdict::AlreadySolved => "not checked, the part is already solved",
+    };
+    println!("day {} part {}: {} is {}", day.number, parts[0], answer, verdict);
+    if response.cooldown > 0 {
+        println!("wait {}s before submitting again", response.cooldown);
+    }
+    if response.verdict != Verdict::Right {
+        process::exit(1);
+    }
+}
+
+// new creates the files for a new day in the source directory.
+fn new(day: u32, src: &Path) {
+    match scaffold::scaffold(src, day) {
+        Ok(files) => {
+            for file in files.iter() {
+                println!("created {}", file.display());
+            }
+            eprintln!("day {} is registered by build.rs on the next build, run `fetch {}` for its input", day, day);
+        },
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        },
+    }
+}
+
+// run_all runs every day on a pool of `jobs` threads and prints a table of the
+// outcomes, exiting with an error if any part failed.
+fn run_all(part: &str, inputs_dir: &Path, jobs: usize) {
+    let part = part.parse().ok();
+    let start = Instant::now();
+    let outcomes = parallel::run_all(DAYS, part, inputs_dir, jobs);
+    let elapsed = start.elapsed();
+    println!("{:>3}  {:>4}  {:<6}  {:>12}  {:>12}  answer", "day", "part", "status", "parse", "solve");
+    for o in outcomes.iter() {
+        match (&o.status, &o.solved) {
+            (parallel::Status::Ok, Some(s @ Solved{answer: Ok(answer), ..})) =>
+                println!("{:>3}  {:>4}  {:<6}  {:>12.2?}  {:>12.2?}  {}",
+                         o.day, o.part, "ok", s.parse_time, s.solve_time, answer),
+            (status, _) => {
+                let (status, detail) = match status {
+                    parallel::Status::Panic(message) => ("PANIC", message.as_str()),
+                    parallel::Status::Error(err) => ("ERROR", err.as_str()),
+                    parallel::Status::Ok => ("ERROR", "no answer"),
+                };
+                let detail = detail.lines().next().unwrap_or("");
+                println!("{:>3}  {:>4}  {:<6}  {:>12}  {:>12}  {}", o.day, o.part, status, "-", "-", detail);
+            },
+        }
+    }
+    let failed = outcomes.iter().filter(|o| o.status != parallel::Status::Ok).count();
+    println!("{} passed, {} failed in {:.2?} on {} threads", outcomes.len() - failed, failed, elapsed, jobs);
+    if failed > 0 {
+        process::exit(1);
+    }
+}
+
+// render draws a day's visualization into `dir`. The files written are reported on
+// stderr so that the answers on stdout are unchanged.
+fn render(day: &Day, input: &str, dir: &Path, format: render::Format) {
+    let mut frames = render::Frames::new();
+    let written = (day.render)(input, &mut frames).and_then(|_| frames.write(dir, format));
+    match written {
+        Ok(paths) => eprintln!("rendered {} files to {}", paths.len(), dir.display()),
+        Err(err) => {
+            eprintln!("error: cannot render day {}: {}", day.number, err);
+            process::exit(1);
+        }
+    }
+}
+
+fn verify(answers_file: &str, record: bool) {
+    let previous = match fs::read_to_string(answers_file) {
+        Ok(s) => verify::parse_answers(&s).unwrap_or_else(|err| {
+            eprintln!("{}", err.with_file(answers_file));
+            process::exit(1);
+        }),
+        Err(_) if record => Vec::new(),
+        Err(err) => {
+            eprintln!("{}: {}", answers_file, err);
+            process::exit(1);
+        }
+    };
+
+    if record {
+        let entries = verify::record(DAYS, &previous).unwrap_or_else(|err| {
+            eprintln!("{}", err);
+            process::exit(1);
+        });
+        if let Err(err) = fs::write(answers_file, verify::format_answers(&entries)) {
+            eprintln!("error: cannot write {}: {}", answers_file, err);
+            process::exit(1);
+        }
+        println!("Recorded {} answers to {}", entries.len(), answers_file);
+        return;
+    }
+
+    let outcomes = verify::verify(DAYS, &previous);
+    println!("{:>3}  {:>4}  {:<7}  answer", "day", "part", "status");
+    for o in outcomes.iter() {
+        let (status, detail) = match &o.status {
+            Status::Pass => ("ok", o.expected.clone().unwrap_or_default()),
+            Status::Fail{got} => ("FAIL", format!("expected {}, got {}", o.expected.as_ref().unwrap(), got)),
+            Status::Missing => ("MISSING", "no expected answer".to_owned()),
+            Status::Error(err) => ("ERROR", err.lines().next().unwrap_or("").to_owned()),
+        };
+        println!("{:>3}  {:>4}  {:<7}  {}", o.day, o.part, status, detail);
+    }
+    let failed = outcomes.iter().filter(|o| o.status != Status::Pass).count();
+    println!("{} passed, {} failed", outcomes.len() - failed, failed);
+    if failed > 0 {
+        process::exit(1);
+    }
+}
+
+fn bench(days: &[&Day], input_file: Option<&str>, inputs_dir: &Path, opts: &bench::Options) {
+    println!("{:>3}  {:<6}  {:>12}  {:>12}  {:>12}", "day", "phase", "min", "median", "max");
+    for day in days {
+        let input = read_input(day, input_file, inputs_dir);
+        let text = input.as_ref().map(|i| i.text.as_str()).unwrap_or("");
+        let timings = (day.bench)(text, opts).unwrap_or_else(|err| {
+            let err = match &input {
+                Some(input) => err.with_file(&input.source),
+                None => err,
+            };
+            eprintln!("{}", err);
+            process::exit(1);
+        });
+        let phases = iter::once(("parse".to_owned(), timings.parse))
+            .chain(timings.parts.iter().map(|(part, stats)| (format!("part {}", part), *stats)));
+        for (phase, stats) in phases {
+            println!("{:>3}  {:<6}  {:>12.2?}  {:>12.2?}  {:>12.2?}",
+                     day.number, phase, stats.min, stats.median, stats.max);
+        }
+    }
+}
+
+fn profile(days: &[&Day], input_file: Option<&str>, inputs_dir: &Path) {
+    if !allocs::ENABLED {
+        eprintln!("error: counting allocations needs the alloc-count feature, e.g.");
+        eprintln!("cargo run --release --features alloc-count -- allocs");
+        process::exit(1);
+    }
+    println!("{:>3}  {:<6}  {:>12}  {:>14}  {:>14}", "day", "phase", "allocations", "bytes", "peak bytes");
+    for day in days {
+        let input = read_input(day, input_file, inputs_dir);
+        let text = input.as_ref().map(|i| i.text.as_str()).unwrap_or("");
+        let profile = (day.profile)(text).unwrap_or_else(|err| {
+            let err = match &input {
+                Some(input) => err.with_file(&input.source),
+                None => err,
+            };
+            eprintln!("{}", err);
+            process::exit(1);
+        });
+        let phases = iter::once(("parse".to_owned(), profile.parse))
+            .chain(profile.parts.iter().map(|(part, stats)| (format!("part {}", part), *stats)));
+        for (phase, stats) in phases {
+            println!("{:>3}  {:<6}  {:>12}  {:>14}  {:>14}",
+                     day.number, phase, stats.allocations, stats.bytes, stats.peak);
+        }
+    }
+}
+
+fn generate(day: &Day, seed: u64, size: usize) {
+    match (day.generate)(&mut gen::Rng::new(seed), size) {
+        Ok(input) => print!("{}", input),
+        Err(err) => {
+            eprintln!("error: cannot generate an input for day {}: {}", day.number, err);
+            process::exit(1);
+        }
+    }
+}
+
 fn main() {
-    println!("Hello, world!");
+    let matches = App::new("AOC2020")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .arg(Arg::with_name("inputs-dir")
+             .long("inputs-dir")
+             .takes_value(true)
+             .global(true)
+             .env("AOC2020_INPUTS")
+             .default_value(input::DEFAULT_DIR)
+             .help("Directory containing dayNN/input.txt, used when --input is not given"))
+        .arg(Arg::with_name("trace")
+             .long("trace")
+             .takes_value(true)
+             .min_values(0)
+             .require_equals(true)
+             .global(true)
+             .value_name("FILTER")
+             .help("Print the solvers' intermediate steps to stderr, e.g. --trace=8,15=debug"))
+        .subcommand(SubCommand::with_name("run")
+            .about("Run one part of a day's solution, or of all days")
+            .arg(Arg::with_name("day")
+                 .required(true)
+                 .index(1)
+                 .help("The day to run, or all to run every day in parallel"))
+            .arg(Arg::with_name("part")
+                 .required(true)
+                 .index(2)
+                 .possible_values(&["1", "2", "both"]))
+            .arg(Arg::with_name("jobs")
+                 .long("jobs")
+                 .short("j")
+                 .takes_value(true)
+                 .help("Number of threads for `run all`, by default one per CPU"))
+            .arg(Arg::with_name("input")
+                 .long("input")
+                 .takes_value(true)
+                 .help("Puzzle input file, or - to read stdin"))
+            .arg(Arg::with_name("watch")
+                 .long("watch")
+                 .conflicts_with("render")
+                 .help("Solve again each time the input file changes"))
+            .arg(Arg::with_name("format")
+                 .long("format")
+                 .takes_value(true)
+                 .possible_values(&["human", "json"])
+                 .default_value("human"))
+            .arg(Arg::with_name("render")
+                 .long("render")
+                 .takes_value(true)
+                 .value_name("DIR")
+                 .help("Write images of how the answers are worked out to DIR"))
+            .arg(Arg::with_name("render-format")
+                 .long("render-format")
+                 .takes_value(true)
+                 .possible_values(&["ppm", "png", "gif"])
+                 .default_value("png")
+                 .help("Image format for --render: PPM or PNG frames, or GIF animations")))
+        .subcommand(SubCommand::with_name("list")
+            .about("List the implemented days and parts"))
+        .subcommand(SubCommand::with_name("fetch")
+            .about("Download a day's puzzle input into the inputs directory")
+            .arg(Arg::with_name("day")
+                 .required(true)
+                 .index(1)))
+        .subcommand(SubCommand::with_name("submit")
+            .about("Solve one part of a day and submit the answer to the server")
+            .arg(Arg::with_name("day")
+                 .required(true)
+                 .index(1))
+            .arg(Arg::with_name("part")
+                 .required(true)
+                 .index(2)
+                 .possible_values(&["1", "2"]))
+            .arg(Arg::with_name("input")
+                 .long("input")
+                 .takes_value(true)
+                 .help("Puzzle input file, or - to read stdin")))
+        .subcommand(SubCommand::with_name("new")
+            .about("Create the module, input file and examples for a new day")
+            .arg(Arg::with_name("day")
+                 .required(true)
+                 .index(1))
+            .arg(Arg::with_name("src")
+                 .long("src")
+                 .takes_value(true)
+                 .default_value("src")
+                 .help("The crate's source directory, which the day is created in")))
+        .subcommand(SubCommand::with_name("bench")
+            .about("Time parsing and each part of one or all days")
+            .arg(Arg::with_name("day")
+                 .index(1))
+            .arg(Arg::with_name("input")
+                 .long("input")
+                 .takes_value(true)
+                 .requires("day"))
+            .arg(Arg::with_name("warmup")
+                 .long("warmup")
+                 .takes_value(true)
+                 .default_value("1"))
+            .arg(Arg::with_name("iterations")
+                 .long("iterations")
+                 .takes_value(true)
+                 .default_value("10")))
+        .subcommand(SubCommand::with_name("allocs")
+            .about("Count the heap allocations of parsing and each part of one or all days")
+            .arg(Arg::with_name("day")
+                 .index(1))
+            .arg(Arg::with_name("input")
+                 .long("input")
+                 .takes_value(true)
+                 .requires("day")))
+        .subcommand(SubCommand::with_name("gen")
+            .about("Write a random puzzle input for a day to stdout")
+            .arg(Arg::with_name("day")
+                 .required(true)
+                 .index(1))
+            .arg(Arg::with_name("seed")
+                 .long("seed")
+                 .takes_value(true)
+                 .default_value("0"))
+            .arg(Arg::with_name("size")
+                 .long("size")
+                 .takes_value(true)
+                 .default_value("100")
+                 .help("Number of lines, records or grid rows")))
+        .subcommand(SubCommand::with_name("verify")
+            .about("Check every day's answers against the answers file")
+            .arg(Arg::with_name("answers")
+                 .long("answers")
+                 .takes_value(true)
+                 .default_value("answers.txt"))
+            .arg(Arg::with_name("record")
+                 .long("record")
+                 .help("Write the current answers to the answers file")))
+        .get_matches();
+
+    let inputs_dir = Path::new(matches.value_of("inputs-dir").unwrap());
+    if matches.is_present("trace") {
+        let filter = matches.value_of("trace").unwrap_or("").parse().unwrap_or_else(|err| {
+            eprintln!("error: --trace: {}", err);
+            process::exit(1);
+        });
+        trace::init(filter).expect("installing the trace logger");
+    }
+    match matches.subcommand() {
+        ("run", Some(m)) if m.value_of("day") == Some("all") => {
+            for arg in ["input", "render", "watch"].iter() {
+                if m.is_present(arg) {
+                    eprintln!("--{} cannot be used with `run all`", arg);
+                    process::exit(1);
+                }
+            }
+            let jobs = match m.value_of("jobs") {
+                Some(jobs) => jobs.parse().ok().filter(|&j| j > 0).unwrap_or_else(|| {
+                    eprintln!("--jobs must be a number of at least 1");
+                    process::exit(1);
+                }),
+                None => thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+            };
+            run_all(m.value_of("part").unwrap(), inputs_dir, jobs);
+        },
+        ("run", Some(m)) => {
+            let number = m.value_of("day").unwrap();
+            let day = find_day(number).unwrap_or_else(|| {
+                eprintln!("day {} is not implemented", number);
+                process::exit(1);
+            });
+            let json = m.value_of("format") == Some("json");
+            let format = m.value_of("render-format").unwrap().parse().unwrap();
+            let render_to = m.value_of("render").map(|dir| (Path::new(dir), format));
+            if m.is_present("watch") {
+                let path = match m.value_of("input") {
+                    Some(input::STDIN) => {
+                        eprintln!("--watch cannot be used with --input -");
+                        process::exit(1);
+                    },
+                    Some(file) => Path::new(file).to_owned(),
+                    None => day.input_in(inputs_dir).unwrap_or_else(|| {
+                        eprintln!("day {} has a built-in input, give one to watch with --input", day.number);
+                        process::exit(1);
+                    }),
+                };
+                watch(day, m.value_of("part").unwrap(), &path, json);
+            }
+            run(day, m.value_of("part").unwrap(), m.value_of("input"), inputs_dir, json, render_to);
+        },
+        ("list", Some(_)) => list(),
+        ("fetch", Some(m)) => {
+            let day = m.value_of("day").unwrap().parse().ok().filter(|d| (1..=25).contains(d)).unwrap_or_else(|| {
+                eprintln!("the day must be a number from 1 to 25");
+                process::exit(1);
+            });
+            fetch(day, inputs_dir);
+        },
+        ("new", Some(m)) => {
+            let day = m.value_of("day").unwrap().parse().unwrap_or_else(|_| {
+                eprintln!("the day must be a number from 1 to 25");
+                process::exit(1);
+            });
+            new(day, Path::new(m.value_of("src").unwrap()));
+        },
+        ("submit", Some(m)) => {
+            let number = m.value_of("day").unwrap();
+            let day = find_day(number).unwrap_or_else(|| {
+                eprintln!("day {} is not implemented", number);
+                process::exit(1);
+            });
+            submit(day, m.value_of("part").unwrap(), m.value_of("input"), inputs_dir);
+        },
+        ("bench", Some(m)) => {
+            let days: Vec<_> = match m.value_of("day") {
+                Some(number) => vec![find_day(number).unwrap_or_else(|| {
+                    eprintln!("day {} is not implemented", number);
+                    process::exit(1);
+                })],
+                None => DAYS.iter().collect(),
+            };
+            let count = |name| m.value_of(name).unwrap().parse::<usize>().unwrap_or_else(|_| {
+                eprintln!("--{} must be a number", name);
+                process::exit(1);
+            });
+            let opts = bench::Options{warmup: count("warmup"), iterations: count("iterations")};
+            bench(&days, m.value_of("input"), inputs_dir, &opts);
+        },
+        ("allocs", Some(m)) => {
+            let days: Vec<_> = match m.value_of("day") {
+                Some(number) => vec![find_day(number).unwrap_or_else(|| {
+                    eprintln!("day {} is not implemented", number);
+                    process::exit(1);
+                })],
+                None => DAYS.iter().collect(),
+            };
+            profile(&days, m.value_of("input"), inputs_dir);
+        },
+        ("gen", Some(m)) => {
+            let number = m.value_of("day").unwrap();
+            let day = find_day(number).unwrap_or_else(|| {
+                eprintln!("day {} is not implemented", number);
+                process::exit(1);
+            });
+            let seed = m.value_of("seed").unwrap().parse().unwrap_or_else(|_| {
+                eprintln!("--seed must be a number");
+                process::exit(1);
+            });
+            let size = m.value_of("size").unwrap().parse().ok().filter(|&s| s > 0).unwrap_or_else(|| {
+                eprintln!("--size must be a number of at least 1");
+                process::exit(1);
+            });
+            generate(day, seed, size);
+        },
+        ("verify", Some(m)) => verify(m.value_of("answers").unwrap(), m.is_present("record")),
+        _ => unreachable!(),
+    }
 }
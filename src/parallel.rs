@@ -0,0 +1,169 @@
+use crate::input;
+use crate::solution::{Day, Solved};
+use std::cell::{Cell, RefCell};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::{Mutex, Once};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    /// The input could not be read or parsed, or the part has no answer.
+    Error(String),
+    /// The solution panicked, with the panic's message and location.
+    Panic(String),
+}
+
+/// Outcome is the result of running one part of a day. `solved` is set when the status
+/// is Ok.
+#[derive(Debug)]
+pub struct Outcome {
+    pub day: u32,
+    pub part: u32,
+    pub status: Status,
+    pub solved: Option<Solved>,
+}
+
+thread_local! {
+    // Whether a panic on this thread is caught by solve, which reports it instead.
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    // The message of the last panic on this thread, recorded by the panic hook.
+    static PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+static HOOK: Once = Once::new();
+
+// install_hook adds a panic hook which records the message of a panic caught by solve
+// instead of printing it. Any other panic goes to the hook which was there before. The
+// hook is installed once and left in place, as swapping it while other threads may
+// panic would lose their panics.
+fn install_hook() {
+    HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !CATCHING.with(Cell::get) {
+                return previous(info);
+            }
+            let payload = info.payload();
+            let message = payload.downcast_ref::<&str>().map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "panicked".to_owned());
+            let message = match info.location() {
+                Some(l) => format!("{} at {}:{}", message, l.file(), l.line()),
+                None => message,
+            };
+            PANIC.with(|p| *p.borrow_mut() = Some(message));
+        }));
+    });
+}
+
+// solve runs a part of a day, catching any panic.
+fn solve(day: &Day, part: u32, input: &Result<String, String>) -> Outcome {
+    let outcome = |status, solved| Outcome{day: day.number, part, status, solved};
+    let input = match input {
+        Ok(input) => input,
+        Err(err) => return outcome(Status::Error(err.clone()), None),
+    };
+    CATCHING.with(|c| c.set(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(|| (day.run)(input, &[part])));
+    CATCHING.with(|c| c.set(false));
+    match result {
+        Ok(Ok(mut solved)) => match solved.pop() {
+            Some(Solved{answer: Err(err), ..}) => outcome(Status::Error(err.to_string()), None),
+            solved => outcome(Status::Ok, solved),
+        },
+        Ok(Err(err)) => outcome(Status::Error(err.to_string()), None),
+        Err(_) => {
+            let message = PANIC.with(|p| p.borrow_mut().take()).unwrap_or_default();
+            outcome(Status::Panic(message), None)
+        },
+    }
+}
+
+/// run_all runs every part of every day (or only `part`, if given) on `jobs` threads
+/// and returns the outcomes in order of day and part. Inputs are read from the inputs
+/// directory `dir`. A panic in a day is reported as its outcome rather than stopping
+/// the others, and its message is not printed.
+pub fn run_all(days: &[Day], part: Option<u32>, dir: &Path, jobs: usize) -> Vec<Outcome> {
+    let inputs: Vec<Result<String, String>> = days.iter()
+        .map(|day| input::read(day, None, dir).map(|i| i.map(|i| i.text).unwrap_or_default()))
+        .collect();
+    let work: Vec<(usize, u32)> = days.iter()
+        .enumerate()
+        .flat_map(|(i, day)| day.parts.iter().map(move |&p| (i, p)))
+        .filter(|&(_, p)| part.is_none_or(|part| part == p))
+        .collect();
+
+    install_hook();
+
+    // Each thread takes the next piece of work until there is none left.
+    let next = AtomicUsize::new(0);
+    let outcomes = Mutex::new(Vec::new());
+    thread::scope(|s| {
+        for _ in 0..jobs.max(1) {
+            s.spawn(|| {
+                while let Some(&(i, part)) = work.get(next.fetch_add(1, Ordering::SeqCst)) {
+                    let outcome = solve(&days[i], part, &inputs[i]);
+                    outcomes.lock().unwrap().push(outcome);
+                }
+            });
+        }
+    });
+
+    let mut outcomes = outcomes.into_inner().unwrap();
+    outcomes.sort_by_key(|o| (o.day, o.part));
+    outcomes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Answer, ParseError, Solution, SolveError};
+
+    // Fragile's part 1 answers 1 and its part 2 panics.
+    struct Fragile;
+
+    impl Solution for Fragile {
+        type Input = ();
+
+        const NEEDS_INPUT: bool = false;
+
+        fn parse(_input: &str) -> Result<(), ParseError> {
+            Ok(())
+        }
+
+        fn part1(_input: &()) -> Result<Answer, SolveError> {
+            Ok(1.into())
+        }
+
+        fn part2(_input: &(), _part1: Option<&Answer>) -> Result<Answer, SolveError> {
+            let parts: Vec<Answer> = Vec::new();
+            Ok(parts.last().cloned().unwrap())
+        }
+    }
+
+    #[test]
+    fn run_all_test() {
+        let days = [Day::new::<Fragile>(2), Day::new::<Fragile>(1)];
+        let outcomes = run_all(&days, None, Path::new("."), 3);
+        let summary: Vec<_> = outcomes.iter().map(|o| (o.day, o.part, o.status == Status::Ok)).collect();
+        assert_eq!(summary, vec![(1, 1, true), (1, 2, false), (2, 1, true), (2, 2, false)]);
+        assert_eq!(outcomes[0].solved.as_ref().map(|s| &s.answer), Some(&Ok(Answer::Int(1))));
+        match &outcomes[1].status {
+            Status::Panic(message) => assert!(message.contains("src/parallel.rs"), "{}", message),
+            status => panic!("expected a panic, got {:?}", status),
+        }
+
+        let outcomes = run_all(&days, Some(2), Path::new("."), 1);
+        assert_eq!(outcomes.len(), 2);
+
+        // A panic outside of a day goes to the previous hook rather than being recorded.
+        let recorded = thread::spawn(|| {
+            let _ = panic::catch_unwind(|| panic!("not a day"));
+            PANIC.with(|p| p.borrow_mut().take())
+        }).join().unwrap();
+        assert_eq!(recorded, None);
+    }
+}
@@ -0,0 +1,231 @@
+use crate::error::ParseError;
+use std::{iter, ops::RangeInclusive, str::{self, FromStr}};
+
+/// lines iterates over the non-empty lines of an input along with their 1-based line
+/// numbers.
+pub fn lines(s: &str) -> impl Iterator<Item = (usize, &str)> {
+    s.split('\n')
+        .enumerate()
+        .map(|(i, l)| (i + 1, l.strip_suffix('\r').unwrap_or(l)))
+        .filter(|(_, l)| !l.is_empty())
+}
+
+/// parse_lines parses each non-empty line of an input with `f`, stopping at the first
+/// error and filling in its line number.
+pub fn parse_lines<T, F>(s: &str, mut f: F) -> Result<Vec<T>, ParseError>
+    where F: FnMut(&mut Cursor) -> Result<T, ParseError>
+{
+    lines(s)
+        .map(|(n, l)| {
+            let mut cursor = Cursor::new(l);
+            f(&mut cursor).map_err(|e| e.with_line(n))
+        })
+        .collect()
+}
+
+/// records splits an input into records: runs of non-blank lines separated by one or
+/// more blank lines. Line endings may be `\n` or `\r\n` and trailing whitespace is
+/// ignored.
+pub fn records(s: &str) -> Records<'_> {
+    Records{lines: s.split('\n').enumerate()}
+}
+
+/// Records iterates over the records of an input. See `records`.
+pub struct Records<'a> {
+    lines: iter::Enumerate<str::Split<'a, char>>,
+}
+
+impl<'a> Iterator for Records<'a> {
+    type Item = Record<'a>;
+
+    fn next(&mut self) -> Option<Record<'a>> {
+        let mut lines = Vec::new();
+        for (i, line) in &mut self.lines {
+            let line = line.trim_end();
+            if !line.is_empty() {
+                lines.push((i + 1, line));
+            } else if !lines.is_empty() {
+                break;
+            }
+        }
+        if lines.is_empty() {None} else {Some(Record{lines})}
+    }
+}
+
+/// Record is a group of consecutive non-blank lines along with their 1-based line
+/// numbers in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record<'a> {
+    lines: Vec<(usize, &'a str)>,
+}
+
+impl<'a> Record<'a> {
+    pub fn lines(&self) -> impl Iterator<Item = (usize, &'a str)> + '_ {
+        self.lines.iter().cloned()
+    }
+
+    /// The line numbers of the first and last lines of the record.
+    pub fn span(&self) -> RangeInclusive<usize> {
+        self.lines[0].0..=self.lines[self.lines.len() - 1].0
+    }
+
+    /// parse_lines parses each line of the record with `f`, like the function of the
+    /// same name does for a whole input.
+    pub fn parse_lines<T, F>(&self, mut f: F) -> Result<Vec<T>, ParseError>
+        where F: FnMut(&mut Cursor) -> Result<T, ParseError>
+    {
+        self.lines()
+            .map(|(n, l)| {
+                let mut cursor = Cursor::new(l);
+                f(&mut cursor).map_err(|e| e.with_line(n))
+            })
+            .collect()
+    }
+}
+
+/// Cursor reads a single line of input from left to right. Each method either consumes
+/// what it was asked for or returns a ParseError pointing at the current column.
+pub struct Cursor<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Cursor{text, pos: 0}
+    }
+
+    /// The whole line being read.
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// The unread remainder of the line.
+    pub fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    /// The 1-based column of the next character.
+    pub fn column(&self) -> usize {
+        self.text[..self.pos].chars().count() + 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pos == self.text.len()
+    }
+
+    /// error creates a ParseError at the current column.
+    pub fn error(&self, expected: impl Into<String>) -> ParseError {
+        ParseError::at(self.text, self.column(), expected)
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    /// next_char consumes the next character, which must match `f`.
+    pub fn next_char<F: Fn(char) -> bool>(&mut self, f: F, expected: &str) -> Result<char, ParseError> {
+        match self.peek() {
+            Some(c) if f(c) => {
+                self.pos += c.len_utf8();
+                Ok(c)
+            },
+            _ => Err(self.error(expected)),
+        }
+    }
+
+    pub fn literal(&mut self, lit: &str) -> Result<(), ParseError> {
+        if self.rest().starts_with(lit) {
+            self.pos += lit.len();
+            Ok(())
+        } else {
+            Err(self.error(format!("`{}`", lit)))
+        }
+    }
+
+    pub fn take_while<F: Fn(char) -> bool>(&mut self, f: F) -> &'a str {
+        let rest = self.rest();
+        let n = rest.find(|c| !f(c)).unwrap_or(rest.len());
+        self.pos += n;
+        &rest[..n]
+    }
+
+    /// word consumes a non-empty run of characters matching `f`.
+    pub fn word<F: Fn(char) -> bool>(&mut self, f: F, expected: &str) -> Result<&'a str, ParseError> {
+        let err = self.error(expected);
+        match self.take_while(f) {
+            "" => Err(err),
+            w => Ok(w),
+        }
+    }
+
+    /// number consumes an optionally signed decimal integer.
+    pub fn number<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        let start = self.pos;
+        let err = self.error("a number");
+        if let Some('+') | Some('-') = self.peek() {
+            self.pos += 1;
+        }
+        let digits = self.take_while(|c| c.is_ascii_digit());
+        let s = &self.text[start..self.pos];
+        if digits.is_empty() {
+            self.pos = start;
+            return Err(err);
+        }
+        s.trim_start_matches('+').parse::<T>().map_err(|_| err.found(format!("`{}` (out of range)", s)))
+    }
+
+    /// end checks that the whole line has been read.
+    pub fn end(&self) -> Result<(), ParseError> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error("end of line"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_test() {
+        let mut c = Cursor::new("mem[8] = -11");
+        assert_eq!(c.literal("mem["), Ok(()));
+        assert_eq!(c.number::<usize>(), Ok(8));
+        assert_eq!(c.literal("] = "), Ok(()));
+        assert_eq!(c.number::<i64>(), Ok(-11));
+        assert_eq!(c.end(), Ok(()));
+    }
+
+    #[test]
+    fn cursor_error_test() {
+        let mut c = Cursor::new("acc +x");
+        assert_eq!(c.word(|c| c.is_ascii_lowercase(), "an operation"), Ok("acc"));
+        c.literal(" ").unwrap();
+        let err = c.number::<i64>().unwrap_err();
+        assert_eq!(err.column(), 5);
+        assert_eq!(err.expected(), "a number");
+    }
+
+    #[test]
+    fn parse_lines_test() {
+        let err = parse_lines("1\n2\n\nx\n", |c| c.number::<u32>()).unwrap_err();
+        assert_eq!(err.line(), 4);
+    }
+
+    #[test]
+    fn records_test() {
+        let input = "a\r\nb  \r\n\r\n\n  \r\nc\n\n\n";
+        let rs: Vec<_> = records(input).collect();
+        assert_eq!(rs.len(), 2);
+        assert_eq!(rs[0].lines().collect::<Vec<_>>(), vec![(1, "a"), (2, "b")]);
+        assert_eq!(rs[0].span(), 1..=2);
+        assert_eq!(rs[1].lines().collect::<Vec<_>>(), vec![(6, "c")]);
+        assert_eq!(records("\n\n").count(), 0);
+
+        let err = rs[1].parse_lines(|c| c.number::<u32>()).unwrap_err();
+        assert_eq!(err.line(), 6);
+    }
+}
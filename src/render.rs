@@ -0,0 +1,295 @@
+use crate::grid::Grid;
+#[cfg(feature = "cli")]
+use std::collections::HashMap;
+#[cfg(feature = "cli")]
+use std::{fs, io::{BufWriter, Write}, path::{Path, PathBuf}, str::FromStr};
+
+/// Rgb is the colour of a pixel.
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+pub const GREY: Rgb = [128, 128, 128];
+pub const RED: Rgb = [220, 50, 47];
+pub const GREEN: Rgb = [38, 139, 34];
+pub const BLUE: Rgb = [38, 110, 210];
+pub const ORANGE: Rgb = [240, 150, 30];
+
+/// Image is a picture of a puzzle's state, one frame of a visualization.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Image{width, height, pixels: vec![background; width * height]}
+    }
+
+    /// from_grid draws a grid with one pixel per cell.
+    pub fn from_grid<T, F: Fn(&T) -> Rgb>(grid: &Grid<T>, colour: F) -> Self {
+        let pixels = grid.iter().map(|(_, cell)| colour(cell)).collect();
+        Image{width: grid.width(), height: grid.height(), pixels}
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    /// set colours a pixel. Pixels off the image are ignored, so that shapes can be
+    /// drawn partly outside it.
+    pub fn set(&mut self, x: i64, y: i64, colour: Rgb) {
+        if x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height {
+            self.pixels[y as usize * self.width + x as usize] = colour;
+        }
+    }
+
+    pub fn fill_rect(&mut self, (x, y): (i64, i64), (width, height): (i64, i64), colour: Rgb) {
+        for dy in 0..height {
+            for dx in 0..width {
+                self.set(x + dx, y + dy, colour);
+            }
+        }
+    }
+
+    /// line draws a straight line between two points, including both ends.
+    pub fn line(&mut self, (x0, y0): (i64, i64), (x1, y1): (i64, i64), colour: Rgb) {
+        let steps = (x1 - x0).abs().max((y1 - y0).abs()).max(1);
+        for i in 0..=steps {
+            let x = x0 + (x1 - x0) * i / steps;
+            let y = y0 + (y1 - y0) * i / steps;
+            self.set(x, y, colour);
+        }
+    }
+
+    /// scaled returns the image with each pixel drawn as a `factor` by `factor` square.
+    pub fn scaled(&self, factor: usize) -> Image {
+        let (width, height) = (self.width * factor, self.height * factor);
+        let pixels = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| self.get(x / factor, y / factor))
+            .collect();
+        Image{width, height, pixels}
+    }
+
+    fn rgb_bytes(&self) -> Vec<u8> {
+        self.pixels.iter().flat_map(|p| p.iter().cloned()).collect()
+    }
+
+    /// ppm encodes the image as a binary PPM.
+    pub fn ppm(&self) -> Vec<u8> {
+        let mut data = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        data.extend(self.rgb_bytes());
+        data
+    }
+
+    #[cfg(feature = "cli")]
+    fn write_png<W: Write>(&self, w: W) -> Result<(), String> {
+        let mut encoder = png::Encoder::new(w, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+        writer.write_image_data(&self.rgb_bytes()).map_err(|e| e.to_string())
+    }
+
+    // gif_frame converts the image to a GIF frame. Puzzle pictures have only a few
+    // colours so a palette is made of them, only falling back to quantizing the colours
+    // when there are too many.
+    #[cfg(feature = "cli")]
+    fn gif_frame(&self) -> Result<gif::Frame<'static>, String> {
+        if self.width > u16::MAX as usize || self.height > u16::MAX as usize {
+            return Err(format!("a {}x{} image is too large for a GIF", self.width, self.height));
+        }
+        let (width, height) = (self.width as u16, self.height as u16);
+        let mut indices = HashMap::new();
+        let mut palette = Vec::new();
+        let mut pixels = Vec::with_capacity(self.pixels.len());
+        for p in self.pixels.iter() {
+            let next = indices.len();
+            let i = *indices.entry(*p).or_insert_with(|| {
+                palette.extend_from_slice(p);
+                next
+            });
+            if i > 255 {
+                return Ok(gif::Frame::from_rgb_speed(width, height, &self.rgb_bytes(), 10));
+            }
+            pixels.push(i as u8);
+        }
+        Ok(gif::Frame::from_palette_pixels(width, height, pixels, palette, None))
+    }
+}
+
+/// Format is the file format visualizations are written in. PPM and PNG write one file
+/// per frame and GIF writes one animation per sequence of frames.
+#[cfg(feature = "cli")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Ppm,
+    Png,
+    Gif,
+}
+
+#[cfg(feature = "cli")]
+impl Format {
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Ppm => "ppm",
+            Format::Png => "png",
+            Format::Gif => "gif",
+        }
+    }
+}
+
+#[cfg(feature = "cli")]
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ppm" => Ok(Format::Ppm),
+            "png" => Ok(Format::Png),
+            "gif" => Ok(Format::Gif),
+            _ => Err(format!("unknown image format `{}`", s)),
+        }
+    }
+}
+
+/// Frames collects the images drawn by a day's visualization as named sequences, such
+/// as each generation of a simulation.
+#[derive(Debug, Default)]
+pub struct Frames {
+    sequences: Vec<(String, Vec<Image>)>,
+}
+
+// The delay between the frames of an animation, in hundredths of a second.
+#[cfg(feature = "cli")]
+const GIF_DELAY: u16 = 10;
+
+impl Frames {
+    pub fn new() -> Self {
+        Frames::default()
+    }
+
+    /// push adds an image to the end of the named sequence.
+    pub fn push(&mut self, name: &str, image: Image) {
+        match self.sequences.iter_mut().find(|(n, _)| n == name) {
+            Some((_, images)) => images.push(image),
+            None => self.sequences.push((name.to_owned(), vec![image])),
+        }
+    }
+
+    pub fn sequences(&self) -> impl Iterator<Item = (&str, &[Image])> {
+        self.sequences.iter().map(|(name, images)| (name.as_str(), images.as_slice()))
+    }
+
+    /// write writes the frames to `dir`, creating it if needed, and returns the paths
+    /// written. A sequence of one frame is written to `NAME.EXT` and longer sequences to
+    /// `NAME-0001.EXT` and so on, or to a single `NAME.gif`.
+    #[cfg(feature = "cli")]
+    pub fn write(&self, dir: &Path, format: Format) -> Result<Vec<PathBuf>, String> {
+        fs::create_dir_all(dir).map_err(|e| format!("cannot create {}: {}", dir.display(), e))?;
+        let mut paths = Vec::new();
+        for (name, images) in self.sequences() {
+            if format == Format::Gif {
+                let path = dir.join(format!("{}.gif", name));
+                write_file(&path, |w| write_gif(w, images))?;
+                paths.push(path);
+                continue;
+            }
+            for (i, image) in images.iter().enumerate() {
+                let path = if images.len() == 1 {
+                    dir.join(format!("{}.{}", name, format.extension()))
+                } else {
+                    dir.join(format!("{}-{:04}.{}", name, i + 1, format.extension()))
+                };
+                write_file(&path, |w| match format {
+                    Format::Png => image.write_png(w),
+                    _ => w.write_all(&image.ppm()).map_err(|e| e.to_string()),
+                })?;
+                paths.push(path);
+            }
+        }
+        Ok(paths)
+    }
+}
+
+// write_file creates a file and writes it with `f`.
+#[cfg(feature = "cli")]
+fn write_file<F>(path: &Path, f: F) -> Result<(), String>
+    where F: FnOnce(&mut BufWriter<fs::File>) -> Result<(), String>
+{
+    let file = fs::File::create(path).map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+    let mut w = BufWriter::new(file);
+    f(&mut w)
+        .and_then(|_| w.flush().map_err(|e| e.to_string()))
+        .map_err(|e| format!("cannot write {}: {}", path.display(), e))
+}
+
+// write_gif writes the images as an animation which loops forever. The animation is as
+// large as the largest frame.
+#[cfg(feature = "cli")]
+fn write_gif<W: Write>(w: W, images: &[Image]) -> Result<(), String> {
+    let width = images.iter().map(|i| i.width).max().unwrap_or(0).min(u16::MAX as usize) as u16;
+    let height = images.iter().map(|i| i.height).max().unwrap_or(0).min(u16::MAX as usize) as u16;
+    let mut encoder = gif::Encoder::new(w, width, height, &[]).map_err(|e| e.to_string())?;
+    encoder.set_repeat(gif::Repeat::Infinite).map_err(|e| e.to_string())?;
+    for image in images {
+        let mut frame = image.gif_frame()?;
+        frame.delay = GIF_DELAY;
+        encoder.write_frame(&frame).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn image_test() {
+        let mut image = Image::new(3, 2, WHITE);
+        image.line((0, 0), (2, 1), RED);
+        image.set(-1, 5, BLUE);
+        assert_eq!(image.get(0, 0), RED);
+        assert_eq!(image.get(2, 1), RED);
+        assert_eq!(image.get(0, 1), WHITE);
+
+        let scaled = image.scaled(2);
+        assert_eq!((scaled.width(), scaled.height()), (6, 4));
+        assert_eq!(scaled.get(1, 1), RED);
+
+        let ppm = Image::new(1, 1, ORANGE).ppm();
+        assert_eq!(ppm, b"P6\n1 1\n255\n\xf0\x96\x1e");
+    }
+
+    #[cfg(feature = "cli")]
+    #[test]
+    fn write_test() {
+        let dir = std::env::temp_dir().join(format!("aoc2020-render-{}", std::process::id()));
+        let mut frames = Frames::new();
+        frames.push("still", Image::new(2, 2, GREEN));
+        frames.push("anim", Image::new(2, 2, BLACK));
+        frames.push("anim", Image::new(2, 2, WHITE));
+
+        let paths = frames.write(&dir, Format::Ppm).unwrap();
+        let names: Vec<_> = paths.iter().map(|p| p.file_name().unwrap().to_str().unwrap()).collect();
+        assert_eq!(names, vec!["still.ppm", "anim-0001.ppm", "anim-0002.ppm"]);
+
+        let paths = frames.write(&dir, Format::Gif).unwrap();
+        assert_eq!(paths.len(), 2);
+        assert!(fs::read(&paths[1]).unwrap().starts_with(b"GIF89a"));
+        let paths = frames.write(&dir, Format::Png).unwrap();
+        assert!(fs::read(&paths[0]).unwrap().starts_with(b"\x89PNG"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
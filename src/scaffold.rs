@@ -0,0 +1,126 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+// The solution module a new day starts from. `{NN}` is replaced by the two digit day
+// number and `{N}` by the number.
+const TEMPLATE: &str = include_str!("../templates/day.rs");
+
+const EXAMPLE_ANSWERS: &str = "# part,answer\n";
+
+// add_feature adds the `dayNN` feature for a day to a manifest, keeping the days in
+// order, and lists it in `all-days`.
+fn add_feature(manifest: &str, day: u32) -> Result<String, String> {
+    let name = format!("day{:02}", day);
+    let mut lines: Vec<String> = manifest.lines().map(str::to_owned).collect();
+    let start = lines.iter().position(|l| l.starts_with("all-days = ["))
+        .ok_or("Cargo.toml has no `all-days` feature")?;
+    let end = start + lines[start..].iter().position(|l| l.trim_end().ends_with(']')).ok_or("`all-days` is not closed")?;
+    let mut days: Vec<String> = lines[start..=end].join(" ")
+        .split('"')
+        .skip(1)
+        .step_by(2)
+        .map(str::to_owned)
+        .collect();
+    if days.contains(&name) {
+        return Err(format!("Cargo.toml already has a `{}` feature", name));
+    }
+    days.push(name.clone());
+    days.sort();
+    let mut list = vec!["all-days = [".to_owned()];
+    for chunk in days.chunks(8) {
+        let quoted: Vec<_> = chunk.iter().map(|d| format!("{:?}", d)).collect();
+        list.push(format!("    {},", quoted.join(", ")));
+    }
+    list.push("]".to_owned());
+    lines.splice(start..=end, list.iter().cloned());
+
+    // The day's own feature goes before the first later day, or after the last day.
+    let after = start + list.len();
+    let end = after + lines[after..].iter().take_while(|l| !l.starts_with('[')).count();
+    let day_lines: Vec<usize> = (after..end).filter(|&i| lines[i].starts_with("day")).collect();
+    let at = day_lines.iter().copied()
+        .find(|&i| lines[i] > name)
+        .unwrap_or_else(|| day_lines.last().map_or(after, |i| i + 1));
+    lines.insert(at, format!("{} = []", name));
+    Ok(lines.join("\n") + "\n")
+}
+
+/// scaffold creates `dayNN` in the source directory `src` for a new day: a solution
+/// module with a test skeleton, an empty `input.txt`, and an `examples` directory with
+/// an empty example to fill in. build.rs picks the day up from there once the day's
+/// feature is added to the `Cargo.toml` next to `src`, which this does too. It returns
+/// the files written, and refuses to touch a day which already exists.
+pub fn scaffold(src: &Path, day: u32) -> Result<Vec<PathBuf>, String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("there is no day {}, days are numbered from 1 to 25", day));
+    }
+    let dir = src.join(format!("day{:02}", day));
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+    let manifest_path = src.parent().unwrap_or_else(|| Path::new("")).join("Cargo.toml");
+    let manifest = match fs::read_to_string(&manifest_path) {
+        Ok(manifest) => Some(add_feature(&manifest, day).map_err(|e| format!("{}: {}", manifest_path.display(), e))?),
+        Err(_) => None,
+    };
+    let module = TEMPLATE.replace("{NN}", &format!("{:02}", day)).replace("{N}", &day.to_string());
+    let examples = dir.join("examples");
+    let files = [
+        (dir.join("mod.rs"), module.as_str()),
+        (dir.join("input.txt"), ""),
+        (examples.join("example1.txt"), ""),
+        (examples.join("example1.answers"), EXAMPLE_ANSWERS),
+    ];
+    fs::create_dir_all(&examples).map_err(|e| format!("cannot create {}: {}", examples.display(), e))?;
+    for (path, text) in files.iter() {
+        // create_new so that nothing is overwritten, even if the day appears meanwhile.
+        OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(text.as_bytes()))
+            .map_err(|e| format!("cannot create {}: {}", path.display(), e))?;
+    }
+    let mut written: Vec<_> = files.iter().map(|(path, _)| path.clone()).collect();
+    if let Some(manifest) = manifest {
+        fs::write(&manifest_path, manifest).map_err(|e| format!("cannot write {}: {}", manifest_path.display(), e))?;
+        written.push(manifest_path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_feature_test() {
+        let manifest = "[features]\ndefault = [\"all-days\"]\nall-days = [\n    \"day01\", \"day03\",\n]\n\
+                        day01 = []\nday03 = [\"regex\"]\n\n# Other.\nother = []\n\n[dev-dependencies]\n";
+        assert_eq!(add_feature(manifest, 2).unwrap(), "[features]\ndefault = [\"all-days\"]\nall-days = [\n    \"day01\", \"day02\", \"day03\",\n]\n\
+                                                     day01 = []\nday02 = []\nday03 = [\"regex\"]\n\n# Other.\nother = []\n\n[dev-dependencies]\n");
+        let added = add_feature(manifest, 20).unwrap();
+        assert!(added.contains("\"day03\", \"day20\",\n]") && added.contains("day03 = [\"regex\"]\nday20 = []\n\n# Other."), "{}", added);
+        assert!(add_feature(manifest, 3).is_err());
+        assert!(add_feature("[features]\n", 3).is_err());
+    }
+
+    #[test]
+    fn scaffold_test() {
+        let src = std::env::temp_dir().join(format!("aoc2020-scaffold-{}", std::process::id()));
+        let files = scaffold(&src, 16).unwrap();
+        assert_eq!(files.len(), 4);
+        let module = fs::read_to_string(src.join("day16").join("mod.rs")).unwrap();
+        assert!(module.contains("pub struct Day16;") && module.contains("day 16 part 2 is not solved yet"));
+        assert!(!module.contains("{N"));
+        assert_eq!(fs::read_to_string(src.join("day16").join("input.txt")).unwrap(), "");
+
+        // An existing day is left alone.
+        fs::write(src.join("day16").join("input.txt"), "1\n").unwrap();
+        assert!(scaffold(&src, 16).unwrap_err().ends_with("day16 already exists"));
+        assert_eq!(fs::read_to_string(src.join("day16").join("input.txt")).unwrap(), "1\n");
+        assert!(scaffold(&src, 26).is_err());
+        fs::remove_dir_all(&src).unwrap();
+    }
+}
@@ -0,0 +1,210 @@
+use crate::allocs::{self, Profile};
+use crate::bench::{self, Timings};
+use crate::error::{ParseError, SolveError};
+use crate::gen::Rng;
+use crate::input;
+use crate::render::Frames;
+use std::{fmt, path::{Path, PathBuf}, time::{Duration, Instant}};
+
+/// Answer is the result of solving one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i128),
+}
+
+impl Answer {
+    pub fn as_int(&self) -> Option<i128> {
+        match *self {
+            Answer::Int(n) => Some(n),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Answer::Int(n as i128)
+            }
+        })*
+    };
+}
+
+answer_from_int!(i32, i64, u32, u64, usize);
+
+/// Solved is the answer to a part, or why there is none, along with how long it took
+/// to parse the input and to solve the part.
+#[derive(Debug, Clone)]
+pub struct Solved {
+    pub part: u32,
+    pub answer: Result<Answer, SolveError>,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+/// Solution is implemented by every day. The puzzle input is parsed once and then
+/// shared by both parts.
+pub trait Solution {
+    type Input;
+
+    /// The parts of the puzzle which have been solved.
+    const PARTS: &'static [u32] = &[1, 2];
+
+    /// Whether the solution reads a puzzle input. Some days have their (short) input
+    /// hardcoded instead.
+    const NEEDS_INPUT: bool = true;
+
+    /// Whether part 2 is solved using the answer to part 1.
+    const PART2_NEEDS_PART1: bool = false;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Result<Answer, SolveError>;
+
+    /// part2 is given the answer to part 1 if it has already been solved. It is always
+    /// given when PART2_NEEDS_PART1 is set, unless part 1 has no answer, in which case
+    /// part 2 fails with the same error without being called.
+    fn part2(input: &Self::Input, part1: Option<&Answer>) -> Result<Answer, SolveError>;
+
+    /// generate writes a random puzzle input. `size` is the number of lines, records
+    /// or grid rows, roughly, and is at least 1.
+    fn generate(_rng: &mut Rng, _size: usize) -> Result<String, String> {
+        Err("there is no input generator".to_owned())
+    }
+
+    /// render draws how the answers are worked out, such as each generation of a
+    /// simulation, into `frames`.
+    fn render(_input: &Self::Input, _frames: &mut Frames) -> Result<(), String> {
+        Err("there is no visualization".to_owned())
+    }
+}
+
+/// Day is a type-erased handle on a day's Solution so that all days can be driven
+/// from the same code.
+pub struct Day {
+    pub number: u32,
+    pub parts: &'static [u32],
+    pub needs_input: bool,
+    pub run: fn(&str, &[u32]) -> Result<Vec<Solved>, ParseError>,
+    pub bench: fn(&str, &bench::Options) -> Result<Timings, ParseError>,
+    pub profile: fn(&str) -> Result<Profile, ParseError>,
+    pub generate: fn(&mut Rng, usize) -> Result<String, String>,
+    pub render: fn(&str, &mut Frames) -> Result<(), String>,
+}
+
+impl Day {
+    pub const fn new<S: Solution>(number: u32) -> Day {
+        Day{
+            number,
+            parts: S::PARTS,
+            needs_input: S::NEEDS_INPUT,
+            run: run::<S>,
+            bench: bench::bench::<S>,
+            profile: allocs::profile::<S>,
+            generate: S::generate,
+            render: render::<S>,
+        }
+    }
+
+    /// The path of the day's puzzle input, or None if it has a built-in input.
+    pub fn default_input(&self) -> Option<PathBuf> {
+        self.input_in(Path::new(crate::input::DEFAULT_DIR))
+    }
+
+    /// The path of the day's puzzle input in the inputs directory `dir`, or None if it
+    /// has a built-in input.
+    pub fn input_in(&self, dir: &Path) -> Option<PathBuf> {
+        if self.needs_input {
+            Some(input::path_in(dir, self.number))
+        } else {
+            None
+        }
+    }
+}
+
+// run parses the input once and solves each of the given parts in order. When part 2
+// needs part 1 but part 1 was not asked for, solving it counts towards part 2's time.
+fn run<S: Solution>(input: &str, parts: &[u32]) -> Result<Vec<Solved>, ParseError> {
+    let start = Instant::now();
+    let input = S::parse(input)?;
+    let parse_time = start.elapsed();
+    let mut part1: Option<Result<Answer, SolveError>> = None;
+    let mut solved = Vec::new();
+    for &part in parts {
+        let start = Instant::now();
+        let answer = if part == 1 {
+            S::part1(&input)
+        } else {
+            if S::PART2_NEEDS_PART1 && part1.is_none() {
+                part1 = Some(S::part1(&input));
+            }
+            match &part1 {
+                Some(Err(err)) if S::PART2_NEEDS_PART1 => Err(err.clone()),
+                _ => S::part2(&input, part1.as_ref().and_then(|a| a.as_ref().ok())),
+            }
+        };
+        if part == 1 {
+            part1 = Some(answer.clone());
+        }
+        solved.push(Solved{part, answer, parse_time, solve_time: start.elapsed()});
+    }
+    Ok(solved)
+}
+
+// render parses the input and draws the day's visualization.
+fn render<S: Solution>(input: &str, frames: &mut Frames) -> Result<(), String> {
+    let input = S::parse(input).map_err(|e| e.to_string())?;
+    S::render(&input, frames)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Double's part 2 is twice its part 1, which is the number in the input.
+    struct Double;
+
+    impl Solution for Double {
+        type Input = i64;
+
+        const PART2_NEEDS_PART1: bool = true;
+
+        fn parse(input: &str) -> Result<i64, ParseError> {
+            input.trim().parse().map_err(|_| ParseError::at(input, 1, "a number"))
+        }
+
+        fn part1(n: &i64) -> Result<Answer, SolveError> {
+            if *n < 0 {
+                return Err(SolveError::NoSolution("negative".to_owned()));
+            }
+            Ok((*n).into())
+        }
+
+        fn part2(_n: &i64, part1: Option<&Answer>) -> Result<Answer, SolveError> {
+            Ok((part1.and_then(Answer::as_int).unwrap() as i64 * 2).into())
+        }
+    }
+
+    #[test]
+    fn run_test() {
+        let day = Day::new::<Double>(0);
+        let answers = |parts: &[u32]| -> Vec<_> {
+            (day.run)("21", parts).unwrap().into_iter().map(|s| (s.part, s.answer)).collect()
+        };
+        assert_eq!(answers(&[2]), vec![(2, Ok(Answer::Int(42)))]);
+        assert_eq!(answers(&[1, 2]), vec![(1, Ok(Answer::Int(21))), (2, Ok(Answer::Int(42)))]);
+        assert!((day.run)("x", &[1]).is_err());
+
+        // Part 2 is not run when part 1 has no answer.
+        let err = Err(SolveError::NoSolution("negative".to_owned()));
+        let solved: Vec<_> = (day.run)("-1", &[2]).unwrap().into_iter().map(|s| s.answer).collect();
+        assert_eq!(solved, vec![err]);
+    }
+}
@@ -0,0 +1,289 @@
+use crate::config::Config;
+use crate::error::ParseError;
+use crate::fetch::{USER_AGENT, YEAR};
+use crate::parse::parse_lines;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Verdict is the server's response to a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// The answer was not checked because the last one was submitted too recently.
+    Wait,
+    /// The part has already been solved, so the answer was not checked.
+    AlreadySolved,
+}
+
+impl Verdict {
+    // is_wrong is whether the answer was checked and found wrong.
+    fn is_wrong(self) -> bool {
+        matches!(self, Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow)
+    }
+}
+
+// Verdicts are written in the history by name.
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Verdict::Right => "right",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::Wait => "wait",
+            Verdict::AlreadySolved => "already-solved",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "right" => Ok(Verdict::Right),
+            "wrong" => Ok(Verdict::Wrong),
+            "too-high" => Ok(Verdict::TooHigh),
+            "too-low" => Ok(Verdict::TooLow),
+            "wait" => Ok(Verdict::Wait),
+            "already-solved" => Ok(Verdict::AlreadySolved),
+            _ => Err(format!("unknown verdict `{}`", s)),
+        }
+    }
+}
+
+/// Response is what the server said about an answer: its verdict, and how many seconds
+/// to wait before submitting another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Response {
+    pub verdict: Verdict,
+    pub cooldown: u64,
+}
+
+/// parse_response reads the verdict and cooldown out of the page the server answers a
+/// submission with. The page is meant for people, so this looks for the sentences
+/// which give the verdict.
+pub fn parse_response(page: &str) -> Result<Response, String> {
+    lazy_static!{
+        static ref LEFT: Regex = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+        static ref WAIT: Regex = Regex::new(r"(?i)wait (one|\d+) minutes? before trying again").unwrap();
+    }
+    let cooldown = if let Some(caps) = LEFT.captures(page) {
+        let minutes = caps.get(1).map_or(0, |m| m.as_str().parse().unwrap_or(0));
+        minutes * 60 + caps[2].parse().unwrap_or(0)
+    } else if let Some(caps) = WAIT.captures(page) {
+        60 * if &caps[1] == "one" {1} else {caps[1].parse().unwrap_or(1)}
+    } else {
+        0
+    };
+    let verdict = if page.contains("That's the right answer") {
+        Verdict::Right
+    } else if page.contains("That's not the right answer") {
+        if page.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if page.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        }
+    } else if page.contains("You gave an answer too recently") {
+        Verdict::Wait
+    } else if page.contains("You don't seem to be solving the right level") {
+        Verdict::AlreadySolved
+    } else {
+        return Err("the server's response has no verdict".to_owned());
+    };
+    Ok(Response{verdict, cooldown})
+}
+
+/// Attempt is one submission recorded in the history. Times are in seconds since the
+/// Unix epoch and `retry_at` is when the server allows the next submission.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    pub time: u64,
+    pub day: u32,
+    pub part: u32,
+    pub verdict: Verdict,
+    pub retry_at: u64,
+    pub answer: String,
+}
+
+/// History is every answer submitted, kept in a file with one attempt per line as
+/// `time,day,part,verdict,retry_at,answer`. The answer comes last since it may contain
+/// commas.
+#[derive(Debug, Clone)]
+pub struct History {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+// parse_history parses the lines of a history file. Lines starting with `#` are
+// comments.
+fn parse_history(s: &str) -> Result<Vec<Attempt>, ParseError> {
+    let attempts = parse_lines(s, |c| {
+        if c.rest().starts_with('#') {
+            return Ok(None);
+        }
+        let time = c.number()?;
+        c.literal(",")?;
+        let day = c.number()?;
+        c.literal(",")?;
+        let part = c.number()?;
+        c.literal(",")?;
+        let err = c.error("a verdict");
+        let verdict = c.take_while(|c| c != ',').parse().map_err(|_| err)?;
+        c.literal(",")?;
+        let retry_at = c.number()?;
+        c.literal(",")?;
+        let answer = c.word(|c| !c.is_whitespace(), "an answer")?.to_owned();
+        c.end()?;
+        Ok(Some(Attempt{time, day, part, verdict, retry_at, answer}))
+    })?;
+    Ok(attempts.into_iter().flatten().collect())
+}
+
+impl History {
+    /// load reads the history file, which is empty if it does not exist yet.
+    pub fn load(path: &Path) -> Result<History, String> {
+        let attempts = match fs::read_to_string(path) {
+            Ok(s) => parse_history(&s).map_err(|e| e.with_file(path).to_string())?,
+            Err(_) => Vec::new(),
+        };
+        Ok(History{path: path.to_owned(), attempts})
+    }
+
+    pub fn attempts(&self) -> &[Attempt] {
+        &self.attempts
+    }
+
+    /// record adds an attempt to the end of the history file.
+    pub fn record(&mut self, attempt: Attempt) -> Result<(), String> {
+        let err = |e: std::io::Error| format!("cannot write {}: {}", self.path.display(), e);
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(err)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path).map_err(err)?;
+        if file.metadata().map_err(err)?.len() == 0 {
+            writeln!(file, "# time,day,part,verdict,retry_at,answer").map_err(err)?;
+        }
+        let a = &attempt;
+        writeln!(file, "{},{},{},{},{},{}", a.time, a.day, a.part, a.verdict, a.retry_at, a.answer).map_err(err)?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+
+    /// check returns why an answer should not be submitted at time `now`, if it should
+    /// not: the server is still cooling down from the last submission, the part is
+    /// already solved, or the answer is known to be wrong. An answer beyond one the
+    /// server said was too high or too low is known to be wrong too.
+    pub fn check(&self, day: u32, part: u32, answer: &str, now: u64) -> Result<(), String> {
+        if let Some(last) = self.attempts.iter().filter(|a| a.retry_at > now).max_by_key(|a| a.retry_at) {
+            return Err(format!("the server asked to wait {}s more before submitting again", last.retry_at - now));
+        }
+        let previous = self.attempts.iter().filter(|a| a.day == day && a.part == part);
+        let n = answer.parse::<i64>().ok();
+        for a in previous {
+            let bound = a.answer.parse::<i64>().ok();
+            let known = match a.verdict {
+                Verdict::Right => return Err(format!("day {} part {} is already solved, with {}", day, part, a.answer)),
+                v if v.is_wrong() && a.answer == answer => Some(v),
+                Verdict::TooHigh if n.is_some() && bound.is_some() && n >= bound => Some(Verdict::TooHigh),
+                Verdict::TooLow if n.is_some() && bound.is_some() && n <= bound => Some(Verdict::TooLow),
+                _ => None,
+            };
+            if let Some(verdict) = known {
+                return Err(format!("{} is known to be {} because {} was {}", answer, verdict, a.answer, verdict));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// answer_url returns the URL answers to a day's puzzle are posted to.
+pub fn answer_url(base_url: &str, day: u32) -> String {
+    format!("{}/{}/day/{}/answer", base_url, YEAR, day)
+}
+
+/// submit posts an answer unless the history says it should not be, and records the
+/// server's verdict in the history. `now` is the time in seconds since the Unix epoch.
+pub fn submit(config: &Config, history: &mut History, day: u32, part: u32, answer: &str, now: u64)
+    -> Result<Response, String>
+{
+    history.check(day, part, answer, now)?;
+    let url = answer_url(&config.base_url, day);
+    let page = ureq::post(&url)
+        .set("Cookie", &format!("session={}", config.session()?))
+        .set("User-Agent", USER_AGENT)
+        .send_form(&[("level", &part.to_string()), ("answer", answer)])
+        .map_err(|e| format!("cannot submit to {}: {}", url, e))?
+        .into_string()
+        .map_err(|e| format!("cannot read the response from {}: {}", url, e))?;
+    let response = parse_response(&page)?;
+    history.record(Attempt{
+        time: now,
+        day,
+        part,
+        verdict: response.verdict,
+        retry_at: now + response.cooldown,
+        answer: answer.to_owned(),
+    })?;
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_response_test() {
+        let response = |page: &str| parse_response(page).map(|r| (r.verdict, r.cooldown));
+        assert_eq!(response("<p>That's the right answer!  You are one gold star closer.</p>"), Ok((Verdict::Right, 0)));
+        assert_eq!(
+            response("<p>That's not the right answer; your answer is too high.  Please wait one minute before trying again.</p>"),
+            Ok((Verdict::TooHigh, 60)));
+        assert_eq!(
+            response("<p>That's not the right answer.  Please wait 5 minutes before trying again.</p>"),
+            Ok((Verdict::Wrong, 300)));
+        assert_eq!(
+            response("<p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 7s left to wait.</p>"),
+            Ok((Verdict::Wait, 67)));
+        assert_eq!(response("You don't seem to be solving the right level."), Ok((Verdict::AlreadySolved, 0)));
+        assert!(response("<html>500</html>").is_err());
+    }
+
+    #[test]
+    fn check_test() {
+        let attempts = parse_history("# time,day,part,verdict,retry_at,answer\n\
+            100,7,1,too-high,160,500\n\
+            200,7,1,too-low,200,100\n\
+            300,9,2,right,360,a,b\n").unwrap();
+        let history = History{path: PathBuf::new(), attempts};
+        assert_eq!(history.attempts()[2].answer, "a,b");
+
+        assert!(history.check(7, 1, "200", 330).unwrap_err().contains("wait 30s"));
+        assert_eq!(history.check(7, 1, "200", 400), Ok(()));
+        assert_eq!(history.check(7, 2, "500", 400), Ok(()));
+        assert_eq!(history.check(7, 1, "600", 400), Err("600 is known to be too-high because 500 was too-high".to_owned()));
+        assert!(history.check(7, 1, "100", 400).is_err());
+        assert!(history.check(7, 1, "abc", 400).is_ok());
+        assert!(history.check(9, 2, "c", 400).unwrap_err().contains("already solved"));
+
+        // A wrong answer which is not a number bounds nothing.
+        let attempts = parse_history("# time,day,part,verdict,retry_at,answer\n\
+            100,7,1,too-high,160,abc\n\
+            200,7,1,too-low,260,xyz\n").unwrap();
+        let history = History{path: PathBuf::new(), attempts};
+        assert_eq!(history.check(7, 1, "-5", 400), Ok(()));
+        assert_eq!(history.check(7, 1, "5", 400), Ok(()));
+        assert!(history.check(7, 1, "abc", 400).unwrap_err().contains("known to be too-high"));
+    }
+}
@@ -0,0 +1,136 @@
+use log::{kv, Level, LevelFilter, Log, Metadata, Record};
+use std::str::FromStr;
+
+/// Filter decides which trace events are printed. It is parsed from a comma separated
+/// list of directives: `8=debug` sets the level for one day, `8` on its own shows all of
+/// a day's events and `info` sets the level for every day not named. An empty filter
+/// shows everything.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filter {
+    default: LevelFilter,
+    days: Vec<(u32, LevelFilter)>,
+}
+
+// parse_day parses a day as either `8` or `day08`.
+fn parse_day(s: &str) -> Option<u32> {
+    s.strip_prefix("day").unwrap_or(s).parse().ok()
+}
+
+impl FromStr for Filter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut days = Vec::new();
+        let mut default = None;
+        for directive in s.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            let level = |s: &str| LevelFilter::from_str(s).map_err(|_| format!("unknown trace level `{}`", s));
+            match directive.split_once('=') {
+                Some((day, lvl)) => {
+                    let day = parse_day(day).ok_or_else(|| format!("unknown day `{}`", day))?;
+                    days.push((day, level(lvl)?));
+                },
+                None => match parse_day(directive) {
+                    Some(day) => days.push((day, LevelFilter::Trace)),
+                    None => default = Some(level(directive)?),
+                },
+            }
+        }
+        // Naming only some days hides the rest unless a level is given for them.
+        let default = match default {
+            Some(level) => level,
+            None if days.is_empty() => LevelFilter::Trace,
+            None => LevelFilter::Off,
+        };
+        Ok(Filter{default, days})
+    }
+}
+
+// day_of returns the day an event comes from, given its target, such as
+// `aoc2020::day08`.
+fn day_of(target: &str) -> Option<u32> {
+    target.split("::").find_map(|m| m.strip_prefix("day")?.parse().ok())
+}
+
+impl Filter {
+    fn level(&self, day: Option<u32>) -> LevelFilter {
+        day.and_then(|day| self.days.iter().rev().find(|(d, _)| *d == day))
+            .map(|&(_, level)| level)
+            .unwrap_or(self.default)
+    }
+
+    pub fn enabled(&self, target: &str, level: Level) -> bool {
+        level <= self.level(day_of(target))
+    }
+
+    fn max_level(&self) -> LevelFilter {
+        self.days.iter().map(|&(_, level)| level).fold(self.default, Ord::max)
+    }
+}
+
+// Logger writes trace events to stderr, one per line, with their key-value pairs.
+struct Logger(Filter);
+
+// Pairs writes an event's key-value pairs as ` key=value`.
+struct Pairs(String);
+
+impl<'kvs> kv::VisitSource<'kvs> for Pairs {
+    fn visit_pair(&mut self, key: kv::Key<'kvs>, value: kv::Value<'kvs>) -> Result<(), kv::Error> {
+        self.0.push_str(&format!(" {}={}", key, value));
+        Ok(())
+    }
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.0.enabled(metadata.target(), metadata.level())
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let mut pairs = Pairs(String::new());
+        let _ = record.key_values().visit(&mut pairs);
+        let source = match day_of(record.target()) {
+            Some(day) => format!("day{:02}", day),
+            None => record.target().to_owned(),
+        };
+        eprintln!("{:<5} {}: {}{}", record.level(), source, record.args(), pairs.0);
+    }
+
+    fn flush(&self) {}
+}
+
+/// init installs a logger which prints the trace events allowed by `filter` to stderr.
+pub fn init(filter: Filter) -> Result<(), String> {
+    let max_level = filter.max_level();
+    log::set_boxed_logger(Box::new(Logger(filter))).map_err(|e| e.to_string())?;
+    log::set_max_level(max_level);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filter_test() {
+        let filter: Filter = "".parse().unwrap();
+        assert!(filter.enabled("aoc2020::day15", Level::Trace));
+
+        let filter: Filter = "8, day15=debug".parse().unwrap();
+        assert!(filter.enabled("aoc2020::day08", Level::Trace));
+        assert!(filter.enabled("aoc2020::day15", Level::Debug));
+        assert!(!filter.enabled("aoc2020::day15", Level::Trace));
+        assert!(!filter.enabled("aoc2020::day07", Level::Error));
+        assert_eq!(filter.max_level(), LevelFilter::Trace);
+
+        let filter: Filter = "info,18=trace".parse().unwrap();
+        assert!(filter.enabled("aoc2020::day07", Level::Info));
+        assert!(!filter.enabled("aoc2020::day07", Level::Debug));
+        assert!(filter.enabled("aoc2020::day18", Level::Trace));
+
+        assert!("loud".parse::<Filter>().is_err());
+        assert!("8=loud".parse::<Filter>().is_err());
+    }
+}
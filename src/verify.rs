@@ -0,0 +1,135 @@
+use crate::error::ParseError;
+use crate::parse::parse_lines;
+use crate::solution::Day;
+use std::{fs, path::PathBuf};
+
+/// Expected is one entry of the answers file: the answer a day and part should give
+/// for an input. Days with a built-in input have no input path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expected {
+    pub day: u32,
+    pub part: u32,
+    pub input: Option<PathBuf>,
+    pub answer: String,
+}
+
+/// parse_answers parses an answers file. Each line is `day,part,input,answer` and lines
+/// starting with `#` are comments.
+pub fn parse_answers(s: &str) -> Result<Vec<Expected>, ParseError> {
+    let entries = parse_lines(s, |c| {
+        if c.rest().starts_with('#') {
+            return Ok(None);
+        }
+        let day = c.number()?;
+        c.literal(",")?;
+        let part = c.number()?;
+        c.literal(",")?;
+        let input = c.take_while(|c| c != ',');
+        c.literal(",")?;
+        let answer = c.word(|c| !c.is_whitespace(), "an answer")?;
+        c.end()?;
+        let input = if input.is_empty() {None} else {Some(PathBuf::from(input))};
+        Ok(Some(Expected{day, part, input, answer: answer.to_owned()}))
+    })?;
+    Ok(entries.into_iter().flatten().collect())
+}
+
+/// format_answers writes the entries in the format read by parse_answers.
+pub fn format_answers(entries: &[Expected]) -> String {
+    let mut s = String::from("# day,part,input,answer\n");
+    for e in entries {
+        let input = e.input.as_ref().map(|p| p.display().to_string()).unwrap_or_default();
+        s.push_str(&format!("{},{},{},{}\n", e.day, e.part, input, e.answer));
+    }
+    s
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail {got: String},
+    Missing,
+    Error(String),
+}
+
+/// Outcome is the result of checking one day and part against the answers file.
+#[derive(Debug)]
+pub struct Outcome {
+    pub day: u32,
+    pub part: u32,
+    pub expected: Option<String>,
+    pub status: Status,
+}
+
+// solve reads the input for a day (if it needs one) and runs the given part.
+fn solve(day: &Day, part: u32, input: Option<&PathBuf>) -> Result<String, String> {
+    let input = match input {
+        Some(path) => fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?,
+        None if day.needs_input => return Err("no input file".to_owned()),
+        None => String::new(),
+    };
+    let solved = (day.run)(&input, &[part]).map_err(|e| e.to_string())?;
+    solved[0].answer.as_ref().map(|a| a.to_string()).map_err(|e| e.to_string())
+}
+
+/// verify runs every part of every day and compares the answers to those expected.
+/// Parts with no expected answer are reported as missing.
+pub fn verify(days: &[Day], expected: &[Expected]) -> Vec<Outcome> {
+    let mut outcomes = Vec::new();
+    for day in days {
+        for &part in day.parts {
+            let e = expected.iter().find(|e| e.day == day.number && e.part == part);
+            let status = match e {
+                None => Status::Missing,
+                Some(e) => match solve(day, part, e.input.as_ref()) {
+                    Ok(got) if got == e.answer => Status::Pass,
+                    Ok(got) => Status::Fail{got},
+                    Err(err) => Status::Error(err),
+                },
+            };
+            outcomes.push(Outcome{day: day.number, part, expected: e.map(|e| e.answer.clone()), status});
+        }
+    }
+    outcomes
+}
+
+/// record runs every part of every day and returns the answers as new expected
+/// entries. The input for each part is taken from `previous` if it has an entry,
+/// otherwise `src/dayNN/input.txt` is used.
+pub fn record(days: &[Day], previous: &[Expected]) -> Result<Vec<Expected>, String> {
+    let mut entries = Vec::new();
+    for day in days {
+        for &part in day.parts {
+            let input = match previous.iter().find(|e| e.day == day.number && e.part == part) {
+                Some(e) => e.input.clone(),
+                None => day.default_input(),
+            };
+            let answer = solve(day, part, input.as_ref())
+                .map_err(|e| format!("day {} part {}: {}", day.number, part, e))?;
+            entries.push(Expected{day: day.number, part, input, answer});
+        }
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_round_trip_test() {
+        let entries = vec![
+            Expected{day: 1, part: 2, input: Some(PathBuf::from("src/day01/input.txt")), answer: "42".to_owned()},
+            Expected{day: 13, part: 1, input: None, answer: "7".to_owned()},
+        ];
+        let s = format_answers(&entries);
+        assert_eq!(s, "# day,part,input,answer\n1,2,src/day01/input.txt,42\n13,1,,7\n");
+        assert_eq!(parse_answers(&s), Ok(entries));
+    }
+
+    #[test]
+    fn parse_answers_error_test() {
+        let err = parse_answers("# comment\n1,x,,5\n").unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 3));
+    }
+}
@@ -0,0 +1,97 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+// How long to wait for more events after a change, so that an editor's save, which may
+// create, write and rename files, is seen as one change.
+const SETTLE: Duration = Duration::from_millis(50);
+
+/// Watcher waits for changes to a file using inotify. The file's directory is watched
+/// rather than the file itself so that changes are still seen when an editor saves by
+/// replacing the file, or the file is deleted and written again.
+pub struct Watcher {
+    path: PathBuf,
+    #[cfg(target_os = "linux")]
+    inotify: inotify::Inotify,
+}
+
+impl Watcher {
+    #[cfg(target_os = "linux")]
+    pub fn new(path: &Path) -> Result<Self, String> {
+        use inotify::{Inotify, WatchMask};
+
+        let dir = match path.parent() {
+            Some(dir) if dir != Path::new("") => dir,
+            _ => Path::new("."),
+        };
+        let inotify = Inotify::init().map_err(|e| format!("cannot start inotify: {}", e))?;
+        inotify.watches()
+            .add(dir, WatchMask::CLOSE_WRITE | WatchMask::MOVED_TO | WatchMask::CREATE)
+            .map_err(|e| format!("cannot watch {}: {}", dir.display(), e))?;
+        Ok(Watcher{path: path.to_owned(), inotify})
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn new(path: &Path) -> Result<Self, String> {
+        let _ = path;
+        Err("watching needs inotify, which is only available on Linux".to_owned())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// wait blocks until the file changes.
+    #[cfg(target_os = "linux")]
+    pub fn wait(&mut self) -> Result<(), String> {
+        let name = self.path.file_name();
+        let mut buffer = [0; 4096];
+        loop {
+            let events = self.inotify.read_events_blocking(&mut buffer)
+                .map_err(|e| format!("cannot watch {}: {}", self.path.display(), e))?;
+            if events.into_iter().any(|e| e.name.is_some() && e.name == name) {
+                break;
+            }
+        }
+        // Drop the rest of the events from the same change.
+        std::thread::sleep(SETTLE);
+        while let Ok(events) = self.inotify.read_events(&mut buffer) {
+            if events.into_iter().next().is_none() {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn wait(&mut self) -> Result<(), String> {
+        Err("watching needs inotify, which is only available on Linux".to_owned())
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use std::{fs, thread};
+
+    #[test]
+    fn wait_test() {
+        let dir = std::env::temp_dir().join(format!("aoc2020-watch-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("input.txt");
+        fs::write(&path, "1\n").unwrap();
+        let mut watcher = Watcher::new(&path).unwrap();
+
+        // Neither another file changing nor the file being replaced by a rename is missed.
+        let (other, temp) = (dir.join("other.txt"), dir.join("input.txt.tmp"));
+        let writer = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(100));
+            fs::write(&other, "x\n").unwrap();
+            fs::write(&temp, "2\n").unwrap();
+            fs::rename(&temp, &path).unwrap();
+        });
+        watcher.wait().unwrap();
+        writer.join().unwrap();
+        assert_eq!(fs::read_to_string(watcher.path()).unwrap(), "2\n");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
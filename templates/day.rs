@@ -0,0 +1,34 @@
+use crate::{Answer, ParseError, Solution, SolveError};
+use crate::parse::parse_lines;
+
+pub struct Day{NN};
+
+impl Solution for Day{NN} {
+    type Input = Vec<String>;
+
+    // Add each part here once it is solved, so that it is run.
+    const PARTS: &'static [u32] = &[];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(input, |c| Ok(c.text().to_owned()))
+    }
+
+    fn part1(_input: &Self::Input) -> Result<Answer, SolveError> {
+        Err(SolveError::NoSolution("day {N} part 1 is not solved yet".to_owned()))
+    }
+
+    fn part2(_input: &Self::Input, _part1: Option<&Answer>) -> Result<Answer, SolveError> {
+        Err(SolveError::NoSolution("day {N} part 2 is not solved yet".to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        let input = Day{NN}::parse("first\nsecond\n").unwrap();
+        assert_eq!(input.len(), 2);
+    }
+}
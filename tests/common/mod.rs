@@ -0,0 +1,64 @@
+//! A stand-in for the puzzle server, for testing the HTTP clients against.
+
+// Each test file uses a different part of this.
+#![allow(dead_code)]
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tiny_http::{Header, Response, Server};
+
+/// Seen is a request the server received.
+#[derive(Debug, Clone)]
+pub struct Seen {
+    pub method: String,
+    pub url: String,
+    pub cookie: Option<String>,
+    pub body: String,
+}
+
+/// MockServer answers every request with a handler on a thread of its own, keeping
+/// the requests it has seen. It stops when dropped.
+pub struct MockServer {
+    pub base_url: String,
+    server: Arc<Server>,
+    seen: Arc<Mutex<Vec<Seen>>>,
+}
+
+impl MockServer {
+    /// start starts a server on a free local port. The handler returns the status and
+    /// body of the response to a request.
+    pub fn start<F>(handler: F) -> MockServer
+        where F: Fn(&Seen) -> (u16, String) + Send + 'static
+    {
+        let server = Arc::new(Server::http("127.0.0.1:0").unwrap());
+        let base_url = format!("http://{}", server.server_addr());
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let (s, requests) = (server.clone(), seen.clone());
+        thread::spawn(move || {
+            for mut request in s.incoming_requests() {
+                let mut body = String::new();
+                request.as_reader().read_to_string(&mut body).unwrap();
+                let cookie = request.headers().iter()
+                    .find(|h| h.field.equiv("Cookie"))
+                    .map(|h| h.value.to_string());
+                let seen = Seen{method: request.method().to_string(), url: request.url().to_owned(), cookie, body};
+                let (status, body) = handler(&seen);
+                requests.lock().unwrap().push(seen);
+                let content_type = Header::from_bytes("Content-Type", "text/plain").unwrap();
+                let response = Response::from_string(body).with_status_code(status).with_header(content_type);
+                let _ = request.respond(response);
+            }
+        });
+        MockServer{base_url, server, seen}
+    }
+
+    pub fn seen(&self) -> Vec<Seen> {
+        self.seen.lock().unwrap().clone()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+    }
+}
@@ -0,0 +1,23 @@
+//! Checks every day against the worked examples in `src/dayNN/examples`. build.rs
+//! generates a test for each part listed in an example's `.answers` file, such as
+//! `day07_example2_part2`.
+
+use aoc2020::DAYS;
+use std::fs;
+
+// check is not called when none of the days built have examples.
+#[allow(dead_code)]
+fn check(number: u32, part: u32, example: &str, expected: &str) {
+    let day = DAYS.iter().find(|d| d.number == number).unwrap();
+    assert!(day.parts.contains(&part), "day {} part {} is not implemented", number, part);
+    let input = fs::read_to_string(example).unwrap();
+    match (day.run)(&input, &[part]) {
+        Ok(solved) => match &solved[0].answer {
+            Ok(answer) => assert_eq!(answer.to_string(), expected, "{}", example),
+            Err(err) => panic!("{}: {}", example, err),
+        },
+        Err(err) => panic!("{}", err.with_file(example)),
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/examples.rs"));
@@ -0,0 +1,48 @@
+//! Tests `fetch` against a local stand-in for the puzzle server.
+
+mod common;
+
+use aoc2020::config::Config;
+use aoc2020::fetch::{self, Cache};
+use common::MockServer;
+use std::{env, fs, path::PathBuf};
+
+fn config(server: &MockServer, name: &str) -> Config {
+    let cache_dir = env::temp_dir().join(format!("aoc2020-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&cache_dir);
+    let history = cache_dir.join("history.csv");
+    Config{session: Some("s3cret".to_owned()), base_url: server.base_url.clone(), cache_dir, history}
+}
+
+#[test]
+fn fetch_once_test() {
+    let server = MockServer::start(|seen| match seen.url.as_str() {
+        "/2020/day/15/input" => (200, "12,1,16,3,11,0\n".to_owned()),
+        _ => (404, "404 Not Found\n".to_owned()),
+    });
+    let config = config(&server, "fetch-once");
+
+    let fetched = fetch::fetch(&config, 15).unwrap();
+    assert_eq!((fetched.text.as_str(), fetched.cached), ("12,1,16,3,11,0\n", false));
+    let fetched = fetch::fetch(&config, 15).unwrap();
+    assert!(fetched.cached);
+
+    // The server was only asked once, with the session cookie.
+    let seen = server.seen();
+    assert_eq!(seen.len(), 1);
+    assert_eq!((seen[0].method.as_str(), seen[0].cookie.as_deref()), ("GET", Some("session=s3cret")));
+    assert_eq!(Cache::new(&config.cache_dir).get(15), Some(fetched.text));
+
+    let err = fetch::fetch(&config, 16).unwrap_err();
+    assert!(err.ends_with("/2020/day/16/input: the server answered 404 404 Not Found"), "{}", err);
+    fs::remove_dir_all(&config.cache_dir).unwrap();
+}
+
+#[test]
+fn no_session_test() {
+    let server = MockServer::start(|_| (200, String::new()));
+    let config = Config{session: None, ..config(&server, "no-session")};
+    assert!(fetch::fetch(&config, 1).unwrap_err().contains("AOC2020_SESSION"));
+    assert!(server.seen().is_empty());
+    assert!(!PathBuf::from(&config.cache_dir).exists());
+}
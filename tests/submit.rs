@@ -0,0 +1,47 @@
+//! Tests `submit` against a local stand-in for the puzzle server.
+
+mod common;
+
+use aoc2020::config::Config;
+use aoc2020::submit::{self, History, Verdict};
+use common::MockServer;
+use std::{env, fs};
+
+fn config(server: &MockServer, name: &str) -> Config {
+    let cache_dir = env::temp_dir().join(format!("aoc2020-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&cache_dir);
+    let history = cache_dir.join("history.csv");
+    Config{session: Some("s3cret".to_owned()), base_url: server.base_url.clone(), cache_dir, history}
+}
+
+#[test]
+fn submit_test() {
+    let server = MockServer::start(|seen| match seen.body.as_str() {
+        "level=1&answer=514579" => (200, "<p>That's the right answer!  You are one gold star closer.</p>".to_owned()),
+        _ => (200, "<p>That's not the right answer; your answer is too low.  \
+                    Please wait one minute before trying again.</p>".to_owned()),
+    });
+    let config = config(&server, "submit");
+    let mut history = History::load(&config.history).unwrap();
+
+    let response = submit::submit(&config, &mut history, 1, 2, "100", 1000).unwrap();
+    assert_eq!((response.verdict, response.cooldown), (Verdict::TooLow, 60));
+    let seen = server.seen();
+    assert_eq!((seen[0].method.as_str(), seen[0].url.as_str()), ("POST", "/2020/day/1/answer"));
+    assert_eq!((seen[0].cookie.as_deref(), seen[0].body.as_str()), (Some("session=s3cret"), "level=2&answer=100"));
+
+    // The attempt is recorded, so nothing is sent during the cooldown or for an answer
+    // known to be wrong.
+    let mut history = History::load(&config.history).unwrap();
+    assert_eq!(history.attempts().len(), 1);
+    assert!(submit::submit(&config, &mut history, 1, 1, "514579", 1030).unwrap_err().contains("wait 30s"));
+    assert!(submit::submit(&config, &mut history, 1, 2, "99", 1100).unwrap_err().contains("too-low"));
+    assert_eq!(server.seen().len(), 1);
+
+    let response = submit::submit(&config, &mut history, 1, 1, "514579", 1100).unwrap();
+    assert_eq!(response.verdict, Verdict::Right);
+    assert!(submit::submit(&config, &mut history, 1, 1, "514579", 1200).unwrap_err().contains("already solved"));
+    assert_eq!(server.seen().len(), 2);
+    assert_eq!(History::load(&config.history).unwrap().attempts(), history.attempts());
+    fs::remove_dir_all(&config.cache_dir).unwrap();
+}